@@ -15,20 +15,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use minimal_template_runtime::{BalancesConfig, SudoConfig, WASM_BINARY};
+use minimal_template_runtime::{
+    genesis_config_presets::STAGING_RUNTIME_PRESET, DECIMALS, WASM_BINARY,
+};
 use polkadot_sdk::{
     sc_service::{ChainType, Properties},
-    sp_keyring::AccountKeyring,
     *,
 };
-use serde_json::{json, Value};
 
 /// This is a specialization of the general Substrate ChainSpec type.
 pub type ChainSpec = sc_service::GenericChainSpec;
 
 fn props() -> Properties {
     let mut properties = Properties::new();
-    properties.insert("tokenDecimals".to_string(), 0.into());
+    properties.insert("tokenDecimals".to_string(), DECIMALS.into());
     properties.insert("tokenSymbol".to_string(), "MINI".into());
     properties
 }
@@ -41,21 +41,33 @@ pub fn development_config() -> Result<ChainSpec, String> {
     .with_name("Development")
     .with_id("dev")
     .with_chain_type(ChainType::Development)
-    .with_genesis_config_patch(testnet_genesis())
+    .with_genesis_config_preset_name(sp_genesis_builder::DEV_RUNTIME_PRESET)
     .with_properties(props())
     .build())
 }
 
-/// Configure initial storage state for FRAME pallets.
-fn testnet_genesis() -> Value {
-    use minimal_template_runtime::interface::{Balance, MinimumBalance};
-    use polkadot_sdk::polkadot_sdk_frame::traits::Get;
-    let endowment = <MinimumBalance as Get<Balance>>::get().max(1) * 1000;
-    let balances = AccountKeyring::iter()
-        .map(|a| (a.to_account_id(), endowment))
-        .collect::<Vec<_>>();
-    json!({
-        "balances": BalancesConfig { balances },
-        "sudo": SudoConfig { key: Some(AccountKeyring::Alice.to_account_id()) },
-    })
+pub fn local_testnet_config() -> Result<ChainSpec, String> {
+    Ok(ChainSpec::builder(
+        WASM_BINARY.expect("Development wasm not available"),
+        Default::default(),
+    )
+    .with_name("Local Testnet")
+    .with_id("local_testnet")
+    .with_chain_type(ChainType::Local)
+    .with_genesis_config_preset_name(sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET)
+    .with_properties(props())
+    .build())
+}
+
+pub fn staging_config() -> Result<ChainSpec, String> {
+    Ok(ChainSpec::builder(
+        WASM_BINARY.expect("Development wasm not available"),
+        Default::default(),
+    )
+    .with_name("Staging Testnet")
+    .with_id("staging")
+    .with_chain_type(ChainType::Live)
+    .with_genesis_config_preset_name(STAGING_RUNTIME_PRESET)
+    .with_properties(props())
+    .build())
 }
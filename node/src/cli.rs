@@ -45,6 +45,12 @@ pub struct Cli {
     #[clap(long, default_value = "manual-seal-3000")]
     pub consensus: Consensus,
 
+    /// Fetch and validate data in the hooks offchain worker without submitting transactions.
+    /// The switch is kept in the offchain storage, set it to `false` with
+    /// `offchain_localStorageSet` to submit again.
+    #[clap(long)]
+    pub hooks_dry_run: bool,
+
     #[clap(flatten)]
     pub run: RunCmd,
 }
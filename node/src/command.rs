@@ -50,6 +50,8 @@ impl SubstrateCli for Cli {
     fn load_spec(&self, id: &str) -> Result<Box<dyn sc_service::ChainSpec>, String> {
         Ok(match id {
             "dev" => Box::new(chain_spec::development_config()?),
+            "local" => Box::new(chain_spec::local_testnet_config()?),
+            "staging" => Box::new(chain_spec::staging_config()?),
             path => Box::new(chain_spec::ChainSpec::from_json_file(
                 std::path::PathBuf::from(path),
             )?),
@@ -139,14 +141,16 @@ pub fn run() -> sc_cli::Result<()> {
             let runner = cli.create_runner(&cli.run)?;
             runner.run_node_until_exit(|config| async move {
                 match config.network.network_backend {
-                    sc_network::config::NetworkBackendType::Libp2p => {
-                        service::new_full::<sc_network::NetworkWorker<_, _>>(config, cli.consensus)
-                            .map_err(sc_cli::Error::Service)
-                    }
+                    sc_network::config::NetworkBackendType::Libp2p => service::new_full::<
+                        sc_network::NetworkWorker<_, _>,
+                    >(
+                        config, cli.consensus, cli.hooks_dry_run
+                    )
+                    .map_err(sc_cli::Error::Service),
                     sc_network::config::NetworkBackendType::Litep2p => service::new_full::<
                         sc_network::Litep2pNetworkBackend,
                     >(
-                        config, cli.consensus
+                        config, cli.consensus, cli.hooks_dry_run
                     )
                     .map_err(sc_cli::Error::Service),
                 }
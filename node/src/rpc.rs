@@ -23,26 +23,34 @@
 #![warn(missing_docs)]
 
 use jsonrpsee::RpcModule;
-use minimal_template_runtime::interface::{AccountId, Nonce, OpaqueBlock};
+use minimal_template_runtime::interface::{
+    AccountId, AssetId, Balance, BlockNumber, Nonce, OpaqueBlock,
+};
 use polkadot_sdk::{
     sc_transaction_pool_api::TransactionPool,
     sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata},
+    sp_core::offchain::OffchainStorage,
     *,
 };
 use std::sync::Arc;
 
+pub mod faucet;
+pub mod hooks;
+
 /// Full client dependencies.
-pub struct FullDeps<C, P> {
+pub struct FullDeps<C, P, S> {
     /// The client instance to use.
     pub client: Arc<C>,
     /// Transaction pool instance.
     pub pool: Arc<P>,
+    /// Offchain local storage of the node, if offchain indexing or workers are enabled.
+    pub offchain_storage: Option<S>,
 }
 
 #[docify::export]
 /// Instantiate all full RPC extensions.
-pub fn create_full<C, P>(
-    deps: FullDeps<C, P>,
+pub fn create_full<C, P, S>(
+    deps: FullDeps<C, P, S>,
 ) -> Result<RpcModule<()>, Box<dyn std::error::Error + Send + Sync>>
 where
     C: Send
@@ -54,13 +62,30 @@ where
         + 'static,
     C::Api: sp_block_builder::BlockBuilder<OpaqueBlock>,
     C::Api: substrate_frame_rpc_system::AccountNonceApi<OpaqueBlock, AccountId, Nonce>,
+    C::Api: hooks_pallet::runtime_api::HooksApi<OpaqueBlock, BlockNumber>,
+    C::Api: pallet_minimal_template::runtime_api::FaucetApi<
+        OpaqueBlock,
+        AccountId,
+        AssetId,
+        Balance,
+        BlockNumber,
+    >,
     P: TransactionPool + 'static,
+    S: OffchainStorage + 'static,
 {
+    use faucet::{Faucet, FaucetApiServer};
+    use hooks::{Hooks, HooksApiServer};
     use polkadot_sdk::substrate_frame_rpc_system::{System, SystemApiServer};
     let mut module = RpcModule::new(());
-    let FullDeps { client, pool } = deps;
+    let FullDeps {
+        client,
+        pool,
+        offchain_storage,
+    } = deps;
 
     module.merge(System::new(client.clone(), pool.clone()).into_rpc())?;
+    module.merge(Faucet::new(client.clone()).into_rpc())?;
+    module.merge(Hooks::new(client.clone(), offchain_storage).into_rpc())?;
 
     Ok(module)
 }
@@ -0,0 +1,151 @@
+//! RPC methods of the faucet pallet, so wallets can show what can still be requested
+//! before submitting `token_faucet`.
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc, types::ErrorObjectOwned};
+use minimal_template_runtime::interface::{
+    AccountId, AssetId, Balance, BlockNumber, Hash, OpaqueBlock,
+};
+use pallet_minimal_template::{runtime_api::FaucetApi as FaucetRuntimeApi, AssetKind};
+use polkadot_sdk::{
+    sp_api::ProvideRuntimeApi, sp_blockchain::HeaderBackend, sp_rpc::number::NumberOrHex, *,
+};
+use std::sync::Arc;
+
+/// Error code returned when the runtime API call fails.
+const RUNTIME_ERROR: i32 = 1;
+
+/// Faucet pallet RPC methods, `asset` is the id of a `pallet-assets` asset or `null` for the
+/// native token.
+///
+/// Balances are returned as hex strings, as JSON numbers can't represent every `u128`.
+#[rpc(server)]
+pub trait FaucetApi {
+    /// Returns the amount of `asset` `who` can still request, `null` if the asset is not enabled.
+    #[method(name = "faucet_remainingAllowance")]
+    fn remaining_allowance(
+        &self,
+        asset: Option<AssetId>,
+        who: AccountId,
+        at: Option<Hash>,
+    ) -> RpcResult<Option<NumberOrHex>>;
+
+    /// Returns the block the window of `who` for `asset` resets at, `null` if the asset is
+    /// not enabled.
+    #[method(name = "faucet_windowReset")]
+    fn window_reset(
+        &self,
+        asset: Option<AssetId>,
+        who: AccountId,
+        at: Option<Hash>,
+    ) -> RpcResult<Option<BlockNumber>>;
+
+    /// Returns the balance of `asset` the faucet can drip.
+    #[method(name = "faucet_balance")]
+    fn balance(&self, asset: Option<AssetId>, at: Option<Hash>) -> RpcResult<NumberOrHex>;
+
+    /// Returns the position of the first queued drip of `who`, `0` being paid next.
+    /// Returns `null` if `who` has no queued drip.
+    #[method(name = "faucet_queuePosition")]
+    fn queue_position(&self, who: AccountId, at: Option<Hash>) -> RpcResult<Option<u32>>;
+
+    /// Returns the budget of `asset` remaining in the current period, `null` if unlimited.
+    #[method(name = "faucet_remainingBudget")]
+    fn remaining_budget(
+        &self,
+        asset: Option<AssetId>,
+        at: Option<Hash>,
+    ) -> RpcResult<Option<NumberOrHex>>;
+}
+
+/// Implementation of [`FaucetApiServer`].
+pub struct Faucet<C> {
+    client: Arc<C>,
+}
+
+impl<C> Faucet<C> {
+    /// Create a new instance querying the runtime of `client`.
+    pub fn new(client: Arc<C>) -> Self {
+        Self { client }
+    }
+}
+
+/// Native token if `asset` is `None`.
+fn asset_kind(asset: Option<AssetId>) -> AssetKind<AssetId> {
+    asset.map_or(AssetKind::Native, AssetKind::Asset)
+}
+
+fn runtime_error(e: impl std::fmt::Display) -> ErrorObjectOwned {
+    ErrorObjectOwned::owned(
+        RUNTIME_ERROR,
+        format!("Unable to query faucet: {e}"),
+        None::<()>,
+    )
+}
+
+impl<C> FaucetApiServer for Faucet<C>
+where
+    C: ProvideRuntimeApi<OpaqueBlock> + HeaderBackend<OpaqueBlock> + Send + Sync + 'static,
+    C::Api: FaucetRuntimeApi<OpaqueBlock, AccountId, AssetId, Balance, BlockNumber>,
+{
+    fn remaining_allowance(
+        &self,
+        asset: Option<AssetId>,
+        who: AccountId,
+        at: Option<Hash>,
+    ) -> RpcResult<Option<NumberOrHex>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        self.client
+            .runtime_api()
+            .remaining_allowance(at, asset_kind(asset), who)
+            .map(|allowance| allowance.map(NumberOrHex::from))
+            .map_err(runtime_error)
+    }
+
+    fn window_reset(
+        &self,
+        asset: Option<AssetId>,
+        who: AccountId,
+        at: Option<Hash>,
+    ) -> RpcResult<Option<BlockNumber>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        self.client
+            .runtime_api()
+            .window_reset(at, asset_kind(asset), who)
+            .map_err(runtime_error)
+    }
+
+    fn balance(&self, asset: Option<AssetId>, at: Option<Hash>) -> RpcResult<NumberOrHex> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        self.client
+            .runtime_api()
+            .faucet_balance(at, asset_kind(asset))
+            .map(NumberOrHex::from)
+            .map_err(runtime_error)
+    }
+
+    fn queue_position(&self, who: AccountId, at: Option<Hash>) -> RpcResult<Option<u32>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        self.client
+            .runtime_api()
+            .queue_position(at, who)
+            .map_err(runtime_error)
+    }
+
+    fn remaining_budget(
+        &self,
+        asset: Option<AssetId>,
+        at: Option<Hash>,
+    ) -> RpcResult<Option<NumberOrHex>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        self.client
+            .runtime_api()
+            .remaining_budget(at, asset_kind(asset))
+            .map(|budget| budget.map(NumberOrHex::from))
+            .map_err(runtime_error)
+    }
+}
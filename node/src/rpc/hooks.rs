@@ -0,0 +1,120 @@
+//! RPC methods of the hooks pallet: stored payloads and the node-local diagnostics
+//! of the offchain worker.
+
+use codec::Decode;
+use hooks_pallet::{
+    diagnostics::{status_key, SourceStatus},
+    runtime_api::HooksApi as HooksRuntimeApi,
+};
+use jsonrpsee::{core::RpcResult, proc_macros::rpc, types::ErrorObjectOwned};
+use minimal_template_runtime::interface::{BlockNumber, Hash, OpaqueBlock};
+use polkadot_sdk::{
+    sp_api::ProvideRuntimeApi,
+    sp_blockchain::HeaderBackend,
+    sp_core::{hexdisplay::HexDisplay, offchain::OffchainStorage, Bytes},
+    *,
+};
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+/// Error code returned when a stored record can't be decoded.
+const DECODE_ERROR: i32 = 1;
+
+/// Error code returned when the runtime API call fails.
+const RUNTIME_ERROR: i32 = 2;
+
+/// Error code returned when the node runs without offchain storage.
+const OFFCHAIN_STORAGE_UNAVAILABLE: i32 = 3;
+
+/// Hooks pallet RPC methods.
+#[rpc(server)]
+pub trait HooksApi {
+    /// Returns the payload saved for `block_number`, reassembled and decompressed.
+    #[method(name = "hooks_payload")]
+    fn payload(&self, block_number: BlockNumber, at: Option<Hash>) -> RpcResult<Option<Bytes>>;
+
+    /// Returns the status of the last offchain worker run for `source`, the default source
+    /// of the pallet if omitted. Returns `null` if the source wasn't processed yet.
+    ///
+    /// `lastTxHash` is the transaction hash of the last submitted `save_data_chunk` extrinsic.
+    #[method(name = "hooks_sourceStatus")]
+    fn source_status(&self, source: Option<String>) -> RpcResult<Option<Value>>;
+}
+
+/// Implementation of [`HooksApiServer`].
+pub struct Hooks<C, S> {
+    client: Arc<C>,
+    storage: Option<S>,
+}
+
+impl<C, S> Hooks<C, S> {
+    /// Create a new instance reading from `client` and the offchain `storage` of the node.
+    pub fn new(client: Arc<C>, storage: Option<S>) -> Self {
+        Self { client, storage }
+    }
+}
+
+impl<C, S> HooksApiServer for Hooks<C, S>
+where
+    C: ProvideRuntimeApi<OpaqueBlock> + HeaderBackend<OpaqueBlock> + Send + Sync + 'static,
+    C::Api: HooksRuntimeApi<OpaqueBlock, BlockNumber>,
+    S: OffchainStorage + 'static,
+{
+    fn payload(&self, block_number: BlockNumber, at: Option<Hash>) -> RpcResult<Option<Bytes>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        self.client
+            .runtime_api()
+            .payload(at, block_number)
+            .map(|payload| payload.map(Into::into))
+            .map_err(|e| {
+                ErrorObjectOwned::owned(
+                    RUNTIME_ERROR,
+                    format!("Unable to query payload: {e}"),
+                    None::<()>,
+                )
+            })
+    }
+
+    fn source_status(&self, source: Option<String>) -> RpcResult<Option<Value>> {
+        let storage = self.storage.as_ref().ok_or_else(|| {
+            ErrorObjectOwned::owned(
+                OFFCHAIN_STORAGE_UNAVAILABLE,
+                "Offchain storage is not available",
+                None::<()>,
+            )
+        })?;
+
+        let source = source.unwrap_or_else(|| hooks_pallet::constants::SOURCE.url.into());
+        let Some(encoded) =
+            storage.get(sp_offchain::STORAGE_PREFIX, &status_key(source.as_bytes()))
+        else {
+            return Ok(None);
+        };
+
+        let status = SourceStatus::decode(&mut &encoded[..]).map_err(|e| {
+            ErrorObjectOwned::owned(
+                DECODE_ERROR,
+                format!("Unable to decode source status: {e}"),
+                None::<()>,
+            )
+        })?;
+
+        Ok(Some(status_to_json(&status)))
+    }
+}
+
+fn status_to_json(status: &SourceStatus) -> Value {
+    json!({
+        "lastAttempt": status.last_attempt,
+        "blockNumber": status.block_number,
+        "httpCode": status.http_code,
+        "bytesRead": status.bytes_read,
+        "chunksSubmitted": status.chunks_submitted,
+        "lastTxHash": status
+            .last_tx_hash
+            .map(|hash| format!("0x{}", HexDisplay::from(&hash))),
+        "dryRun": status.dry_run,
+        "error": status.error.as_ref().map(|e| format!("{e:?}")),
+    })
+}
@@ -15,10 +15,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use codec::Encode;
 use futures::FutureExt;
 use minimal_template_runtime::{interface::OpaqueBlock as Block, RuntimeApi};
 use polkadot_sdk::{
     sc_client_api::backend::Backend,
+    sp_core::offchain::OffchainStorage,
     sc_executor::WasmExecutor,
     sc_service::{error::Error as ServiceError, Configuration, TaskManager},
     sc_telemetry::{Telemetry, TelemetryWorker},
@@ -110,6 +112,7 @@ pub fn new_partial(config: &Configuration) -> Result<Service, ServiceError> {
 pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Hash>>(
     config: Configuration,
     consensus: Consensus,
+    hooks_dry_run: bool,
 ) -> Result<TaskManager, ServiceError> {
     let sc_service::PartialComponents {
         client,
@@ -151,6 +154,15 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
             metrics,
         })?;
 
+    // Without the flag the stored switch is kept, it may have been set over RPC
+    if let Some(mut offchain_storage) = backend.offchain_storage().filter(|_| hooks_dry_run) {
+        offchain_storage.set(
+            sp_offchain::STORAGE_PREFIX,
+            hooks_pallet::diagnostics::DRY_RUN_KEY,
+            &true.encode(),
+        );
+    }
+
     if config.offchain_worker.enabled {
         task_manager.spawn_handle().spawn(
             "offchain-workers-runner",
@@ -175,11 +187,13 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
     let rpc_extensions_builder = {
         let client = client.clone();
         let pool = transaction_pool.clone();
+        let offchain_storage = backend.offchain_storage();
 
         Box::new(move |_| {
             let deps = crate::rpc::FullDeps {
                 client: client.clone(),
                 pool: pool.clone(),
+                offchain_storage: offchain_storage.clone(),
             };
             crate::rpc::create_full(deps).map_err(Into::into)
         })
@@ -0,0 +1,87 @@
+//! Compression of the payloads stored in `DataChunks`.
+//!
+//! The offchain worker compresses the whole body of a source before splitting it into chunks,
+//! so a payload can only be decompressed after all of its chunks were reassembled.
+
+use codec::{alloc::vec::Vec, Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+
+/// Compression level used for deflate, a balance between speed and ratio.
+const DEFLATE_LEVEL: u8 = 6;
+
+/// Compression codec of a stored payload.
+#[derive(
+    Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Copy, PartialEq, Eq, Debug, Default,
+)]
+pub enum Compression {
+    /// Payload is stored as is
+    #[default]
+    None,
+    /// Payload is compressed with raw deflate
+    Deflate,
+}
+
+impl Compression {
+    /// Compresses `data` with the codec
+    pub fn compress(self, data: &[u8]) -> Vec<u8> {
+        match self {
+            Compression::None => Vec::from(data),
+            Compression::Deflate => miniz_oxide::deflate::compress_to_vec(data, DEFLATE_LEVEL),
+        }
+    }
+
+    /// Decompresses `data` with the codec, returns `None` if `data` is malformed
+    /// or the decompressed payload would be longer than `max_len`
+    pub fn decompress(self, data: &[u8], max_len: usize) -> Option<Vec<u8>> {
+        match self {
+            Compression::None => Some(Vec::from(data)),
+            Compression::Deflate => {
+                miniz_oxide::inflate::decompress_to_vec_with_limit(data, max_len).ok()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA: &[u8] = b"{\"jsonrpc\":\"2.0\",\"result\":\"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\"}";
+
+    #[test]
+    fn round_trip_works() {
+        for compression in [Compression::None, Compression::Deflate] {
+            let compressed = compression.compress(DATA);
+
+            assert_eq!(
+                compression.decompress(&compressed, 1024),
+                Some(DATA.to_vec())
+            );
+        }
+    }
+
+    #[test]
+    fn deflate_shrinks_repetitive_data() {
+        assert!(Compression::Deflate.compress(DATA).len() < DATA.len());
+    }
+
+    #[test]
+    fn decompress_respects_max_len() {
+        let compressed = Compression::Deflate.compress(DATA);
+
+        assert_eq!(
+            Compression::Deflate.decompress(&compressed, DATA.len() - 1),
+            None
+        );
+    }
+
+    #[test]
+    fn truncated_deflate_payload_is_rejected() {
+        let compressed = Compression::Deflate.compress(DATA);
+
+        assert_eq!(
+            Compression::Deflate.decompress(&compressed[..compressed.len() / 2], 1024),
+            None
+        );
+    }
+}
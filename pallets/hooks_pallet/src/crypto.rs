@@ -1,30 +1,104 @@
-use polkadot_sdk::sp_core::sr25519::Signature as Sr25519Signature;
-use polkadot_sdk::sp_runtime::{
-    app_crypto::{app_crypto, sr25519},
-    key_types::ACCOUNT,
-    traits::Verify,
-    MultiSignature, MultiSigner,
-};
-
-app_crypto!(sr25519, ACCOUNT);
-
-pub struct OffchainAuthId;
-
-impl polkadot_sdk::frame_system::offchain::AppCrypto<MultiSigner, MultiSignature>
-    for OffchainAuthId
-{
-    type RuntimeAppPublic = Public;
-    type GenericPublic = polkadot_sdk::sp_core::sr25519::Public;
-    type GenericSignature = polkadot_sdk::sp_core::sr25519::Signature;
+//! Application crypto used by the offchain worker to sign `save_data_chunk` transactions.
+//!
+//! Every supported signature scheme has its own [`KeyTypeId`], so the offchain worker keys
+//! never collide with account keys and can be rotated with `author_rotateKeys`.
+
+use polkadot_sdk::sp_core::crypto::KeyTypeId;
+
+/// Key type of the sr25519 offchain worker keys.
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"hook");
+
+/// Key type of the ed25519 offchain worker keys.
+pub const ED25519_KEY_TYPE: KeyTypeId = KeyTypeId(*b"hked");
+
+/// Key type of the ecdsa offchain worker keys.
+pub const ECDSA_KEY_TYPE: KeyTypeId = KeyTypeId(*b"hkec");
+
+pub mod sr25519 {
+    use super::KEY_TYPE;
+    use polkadot_sdk::sp_core::sr25519::Signature as Sr25519Signature;
+    use polkadot_sdk::sp_runtime::{
+        app_crypto::{app_crypto, sr25519},
+        traits::Verify,
+        MultiSignature, MultiSigner,
+    };
+
+    app_crypto!(sr25519, KEY_TYPE);
+
+    pub struct OffchainAuthId;
+
+    impl polkadot_sdk::frame_system::offchain::AppCrypto<MultiSigner, MultiSignature>
+        for OffchainAuthId
+    {
+        type RuntimeAppPublic = Public;
+        type GenericPublic = polkadot_sdk::sp_core::sr25519::Public;
+        type GenericSignature = polkadot_sdk::sp_core::sr25519::Signature;
+    }
+
+    impl
+        polkadot_sdk::frame_system::offchain::AppCrypto<
+            <Sr25519Signature as Verify>::Signer,
+            Sr25519Signature,
+        > for OffchainAuthId
+    {
+        type RuntimeAppPublic = Public;
+        type GenericPublic = polkadot_sdk::sp_core::sr25519::Public;
+        type GenericSignature = polkadot_sdk::sp_core::sr25519::Signature;
+    }
 }
 
-impl
-    polkadot_sdk::frame_system::offchain::AppCrypto<
-        <Sr25519Signature as Verify>::Signer,
-        Sr25519Signature,
-    > for OffchainAuthId
-{
-    type RuntimeAppPublic = Public;
-    type GenericPublic = polkadot_sdk::sp_core::sr25519::Public;
-    type GenericSignature = polkadot_sdk::sp_core::sr25519::Signature;
+pub mod ed25519 {
+    use super::ED25519_KEY_TYPE;
+    use polkadot_sdk::sp_core::ed25519::Signature as Ed25519Signature;
+    use polkadot_sdk::sp_runtime::{
+        app_crypto::{app_crypto, ed25519},
+        traits::Verify,
+        MultiSignature, MultiSigner,
+    };
+
+    app_crypto!(ed25519, ED25519_KEY_TYPE);
+
+    pub struct OffchainAuthId;
+
+    impl polkadot_sdk::frame_system::offchain::AppCrypto<MultiSigner, MultiSignature>
+        for OffchainAuthId
+    {
+        type RuntimeAppPublic = Public;
+        type GenericPublic = polkadot_sdk::sp_core::ed25519::Public;
+        type GenericSignature = polkadot_sdk::sp_core::ed25519::Signature;
+    }
+
+    impl
+        polkadot_sdk::frame_system::offchain::AppCrypto<
+            <Ed25519Signature as Verify>::Signer,
+            Ed25519Signature,
+        > for OffchainAuthId
+    {
+        type RuntimeAppPublic = Public;
+        type GenericPublic = polkadot_sdk::sp_core::ed25519::Public;
+        type GenericSignature = polkadot_sdk::sp_core::ed25519::Signature;
+    }
 }
+
+pub mod ecdsa {
+    use super::ECDSA_KEY_TYPE;
+    use polkadot_sdk::sp_runtime::{
+        app_crypto::{app_crypto, ecdsa},
+        MultiSignature, MultiSigner,
+    };
+
+    app_crypto!(ecdsa, ECDSA_KEY_TYPE);
+
+    pub struct OffchainAuthId;
+
+    impl polkadot_sdk::frame_system::offchain::AppCrypto<MultiSigner, MultiSignature>
+        for OffchainAuthId
+    {
+        type RuntimeAppPublic = Public;
+        type GenericPublic = polkadot_sdk::sp_core::ecdsa::Public;
+        type GenericSignature = polkadot_sdk::sp_core::ecdsa::Signature;
+    }
+}
+
+// sr25519 stays the default scheme of the offchain worker.
+pub use self::sr25519::OffchainAuthId;
@@ -0,0 +1,54 @@
+//! Node-local diagnostics of the offchain worker.
+//!
+//! After every run the offchain worker writes a [`SourceStatus`] record for the processed source
+//! into the persistent offchain local storage, under [`status_key`]. The record can be read with
+//! the `hooks_sourceStatus` RPC of the node.
+//!
+//! Setting the persistent key [`DRY_RUN_KEY`] to a SCALE encoded `true` (e.g. with the
+//! `--hooks-dry-run` node flag or the `offchain_localStorageSet` RPC) makes the worker fetch and
+//! validate the data without submitting any transaction. The node only writes the key when
+//! started with the flag, so the switch persists across restarts until it is set to `false`.
+
+use crate::pallet::{DataProcessingError, HttpRequestError};
+use codec::{alloc::vec::Vec, Decode, Encode};
+
+/// Offchain local storage key of the dry-run switch.
+pub const DRY_RUN_KEY: &[u8] = b"hooks_pallet::dry_run";
+
+/// Prefix of the offchain local storage keys holding [`SourceStatus`] records.
+pub const STATUS_KEY_PREFIX: &[u8] = b"hooks_pallet::status::";
+
+/// Offchain local storage key of the [`SourceStatus`] record of `source`.
+pub fn status_key(source: &[u8]) -> Vec<u8> {
+    [STATUS_KEY_PREFIX, source].concat()
+}
+
+/// Error which interrupted the last offchain worker run.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub enum WorkerError {
+    /// Error while fetching the source
+    Http(HttpRequestError),
+    /// Error while validating or submitting the fetched data
+    DataProcessing(DataProcessingError),
+}
+
+/// Outcome of the last offchain worker run for a source.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, Default)]
+pub struct SourceStatus {
+    /// Unix timestamp of the last attempt in ms
+    pub last_attempt: u64,
+    /// Block number the offchain worker ran for
+    pub block_number: u64,
+    /// Http response code, if a response was received
+    pub http_code: Option<u16>,
+    /// Amount of body bytes read
+    pub bytes_read: u64,
+    /// Amount of `save_data_chunk` transactions submitted
+    pub chunks_submitted: u32,
+    /// Blake2-256 hash of the last submitted `save_data_chunk` extrinsic, its transaction hash
+    pub last_tx_hash: Option<[u8; 32]>,
+    /// Whether the run was a dry run, i.e. nothing was submitted
+    pub dry_run: bool,
+    /// Error which interrupted the run, `None` if it succeeded
+    pub error: Option<WorkerError>,
+}
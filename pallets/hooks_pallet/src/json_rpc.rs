@@ -0,0 +1,156 @@
+//! JSON-RPC 2.0 envelope of [`SourceKind::JsonRpc`](crate::source::SourceKind::JsonRpc) sources.
+
+use crate::pallet::{DataProcessingError, HttpRequestError};
+use codec::alloc::vec::Vec;
+use serde_json::{json, Value};
+
+/// Id of the requests, the offchain worker sends a single call per request.
+pub const REQUEST_ID: u64 = 1;
+
+/// Builds the JSON encoded request body calling `method` with JSON encoded `params`
+pub fn request_body(method: &str, params: &str) -> Result<Vec<u8>, HttpRequestError> {
+    let params: Value =
+        serde_json::from_str(params).map_err(|_| HttpRequestError::InvalidJsonRpcParams)?;
+    if !params.is_array() && !params.is_object() {
+        return Err(HttpRequestError::InvalidJsonRpcParams);
+    }
+
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": REQUEST_ID,
+        "method": method,
+        "params": params,
+    });
+
+    serde_json::to_vec(&request).map_err(|_| HttpRequestError::InvalidJsonRpcParams)
+}
+
+/// Checks the envelope and the id of `response` and returns the JSON encoded `result`,
+/// or its part at `pointer` if set
+pub fn extract_result(
+    response: &[u8],
+    pointer: Option<&str>,
+) -> Result<Vec<u8>, DataProcessingError> {
+    let response: Value = serde_json::from_slice(response)
+        .map_err(|_| DataProcessingError::InvalidJsonRpcResponse)?;
+
+    if response.get("jsonrpc").and_then(Value::as_str) != Some("2.0")
+        || response.get("id").and_then(Value::as_u64) != Some(REQUEST_ID)
+    {
+        return Err(DataProcessingError::InvalidJsonRpcResponse);
+    }
+
+    if let Some(error) = response.get("error") {
+        let code = error
+            .get("code")
+            .and_then(Value::as_i64)
+            .ok_or(DataProcessingError::InvalidJsonRpcResponse)?;
+        return Err(DataProcessingError::JsonRpcError(code));
+    }
+
+    let result = response
+        .get("result")
+        .ok_or(DataProcessingError::InvalidJsonRpcResponse)?;
+
+    let result = match pointer {
+        Some(pointer) => result
+            .pointer(pointer)
+            .ok_or(DataProcessingError::JsonRpcResultNotFound)?,
+        None => result,
+    };
+
+    serde_json::to_vec(result).map_err(|_| DataProcessingError::InvalidJsonRpcResponse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extract(response: &str, pointer: Option<&str>) -> Result<Value, DataProcessingError> {
+        extract_result(response.as_bytes(), pointer)
+            .map(|result| serde_json::from_slice(&result).unwrap())
+    }
+
+    #[test]
+    fn request_body_works() {
+        let body = request_body("chain_getHeader", r#"["0x01"]"#).unwrap();
+
+        assert_eq!(
+            serde_json::from_slice::<Value>(&body).unwrap(),
+            json!({
+                "jsonrpc": "2.0",
+                "id": REQUEST_ID,
+                "method": "chain_getHeader",
+                "params": ["0x01"],
+            })
+        );
+    }
+
+    #[test]
+    fn request_body_rejects_invalid_params() {
+        for params in ["", "[", "1", r#""0x01""#, "null"] {
+            assert_eq!(
+                request_body("chain_getHeader", params),
+                Err(HttpRequestError::InvalidJsonRpcParams)
+            );
+        }
+    }
+
+    #[test]
+    fn extract_result_works() {
+        assert_eq!(
+            extract(
+                r#"{"jsonrpc":"2.0","id":1,"result":{"number":"0x10"}}"#,
+                None
+            ),
+            Ok(json!({ "number": "0x10" }))
+        );
+    }
+
+    #[test]
+    fn extract_result_returns_error_code() {
+        assert_eq!(
+            extract(
+                r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32601,"message":"Method not found"}}"#,
+                None
+            ),
+            Err(DataProcessingError::JsonRpcError(-32601))
+        );
+        assert_eq!(
+            extract(
+                r#"{"jsonrpc":"2.0","id":1,"error":{"message":"No code"}}"#,
+                None
+            ),
+            Err(DataProcessingError::InvalidJsonRpcResponse)
+        );
+    }
+
+    #[test]
+    fn extract_result_checks_envelope() {
+        for response in [
+            "not json",
+            r#"{"id":1,"result":"0x10"}"#,
+            r#"{"jsonrpc":"1.0","id":1,"result":"0x10"}"#,
+            r#"{"jsonrpc":"2.0","result":"0x10"}"#,
+            r#"{"jsonrpc":"2.0","id":2,"result":"0x10"}"#,
+            r#"{"jsonrpc":"2.0","id":1}"#,
+        ] {
+            assert_eq!(
+                extract(response, None),
+                Err(DataProcessingError::InvalidJsonRpcResponse),
+                "{response}"
+            );
+        }
+    }
+
+    #[test]
+    fn extract_result_follows_pointer() {
+        let response = r#"{"jsonrpc":"2.0","id":1,"result":{"header":{"number":"0x10"}}}"#;
+
+        assert_eq!(extract(response, Some("/header/number")), Ok(json!("0x10")));
+        assert_eq!(
+            extract(response, Some("/header/hash")),
+            Err(DataProcessingError::JsonRpcResultNotFound)
+        );
+    }
+}
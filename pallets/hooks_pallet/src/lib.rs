@@ -2,7 +2,17 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+pub mod compression;
 pub mod crypto;
+pub mod diagnostics;
+pub mod json_rpc;
+pub mod runtime_api;
+pub mod source;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
 
 use frame::prelude::*;
 use polkadot_sdk::polkadot_sdk_frame as frame;
@@ -13,21 +23,33 @@ pub use pallet::*;
 #[frame::pallet]
 pub mod pallet {
     use super::*;
+    use crate::compression::Compression;
+    use crate::diagnostics::{self, SourceStatus, WorkerError};
+    use crate::json_rpc;
+    use crate::source::{Source, SourceKind};
     use codec::alloc::{string::String, vec, vec::Vec};
     use frame_system::offchain::{
-        Account, AppCrypto, CreateSignedTransaction, SendSignedTransaction, SendTransactionTypes,
-        Signer,
+        Account, AppCrypto, CreateSignedTransaction, SendTransactionTypes, Signer,
     };
-    use polkadot_sdk::sp_core;
+    use polkadot_sdk::{sp_core, sp_io};
     use polkadot_sdk::sp_io::offchain::{
-        http_request_start, http_response_read_body, http_response_wait, timestamp,
+        http_request_add_header, http_request_start, http_request_write_body,
+        http_response_read_body, http_response_wait, timestamp,
+    };
+    use polkadot_sdk::sp_runtime::offchain::{
+        storage::StorageValueRef, HttpRequestId, HttpRequestStatus,
     };
-    use polkadot_sdk::sp_runtime::offchain::{HttpRequestId, HttpRequestStatus};
-    use polkadot_sdk::sp_runtime::Saturating;
+    use polkadot_sdk::sp_runtime::{
+        traits::Extrinsic as ExtrinsicT, SaturatedConversion, Saturating,
+    };
+    #[cfg(any(feature = "try-runtime", test))]
+    use polkadot_sdk::sp_runtime::TryRuntimeError;
     use sp_core::offchain::{Duration, Timestamp};
     use scale_info::prelude::boxed::Box;
 
     pub mod constants {
+        use super::{Compression, Source, SourceKind};
+
         /// Time limit for waiting response in ms
         pub const RESPONSE_TIME_LIMIT: u64 = 500;
 
@@ -39,6 +61,14 @@ pub mod pallet {
 
         /// Set the criteria for saving a chunk
         pub const TARGET: &'static str = "/";
+
+        /// Source fetched by the offchain worker
+        pub const SOURCE: Source = Source {
+            url: URL,
+            kind: SourceKind::Get,
+            target: TARGET,
+            compression: Compression::Deflate,
+        };
     }
 
     /// (k1: block number, k2: index of data for current block number) : chunk of data
@@ -53,6 +83,11 @@ pub mod pallet {
         ValueQuery,
     >;
 
+    /// block number : metadata of the payload stored in DataChunks for this block number
+    #[pallet::storage]
+    pub type PayloadMetadata<T: Config> =
+        StorageMap<_, Blake2_128Concat, BlockNumberFor<T>, PayloadMeta, OptionQuery>;
+
     /// Current amount of chunks in storage DataChunks
     #[pallet::storage]
     #[pallet::getter(fn current_amount_of_chunks)]
@@ -63,6 +98,11 @@ pub mod pallet {
     #[pallet::getter(fn last_save)]
     pub type LastSave<T: Config> = StorageValue<_, BlockNumberFor<T>, ValueQuery>;
 
+    /// Accounts of the offchain worker keys allowed to save chunks
+    #[pallet::storage]
+    pub type Reporters<T: Config> =
+        StorageValue<_, BoundedVec<T::AccountId, T::MaxReporters>, ValueQuery>;
+
     #[pallet::config]
     pub trait Config:
         polkadot_sdk::frame_system::Config
@@ -83,11 +123,29 @@ pub mod pallet {
 
         /// The identifier type for an offchain worker.
         type OffChainAuthId: AppCrypto<Self::Public, Self::Signature>;
+
+        /// Maximum amount of reporters
+        #[pallet::constant]
+        type MaxReporters: Get<u32>;
+
+        /// Origin managing the reporters and clearing saved payloads
+        type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
     }
 
+    /// The in-code storage version, bump it together with a `VersionedMigration` whenever the
+    /// layout of existing storage changes.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(0);
+
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
+    /// Metadata shared by all chunks of a payload
+    #[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq, Eq, Debug, Default)]
+    pub struct PayloadMeta {
+        /// Compression applied to the payload before splitting it into chunks
+        pub compression: Compression,
+    }
 
     #[pallet::error]
     pub enum Error<T> {
@@ -95,9 +153,33 @@ pub mod pallet {
         VecToBoundedVecConvertationError,
         /// Saved chunks limit exceeded
         ChunksLimitExceeded,
+        /// Chunk compression differs from the compression of the payload it belongs to
+        CompressionMismatch,
+        /// Sender of the chunk isn't a reporter
+        NotReporter,
+        /// Account is already a reporter
+        AlreadyReporter,
+        /// Reporters limit exceeded
+        TooManyReporters,
+    }
+
+    #[pallet::genesis_config]
+    #[derive(DefaultNoBound)]
+    pub struct GenesisConfig<T: Config> {
+        /// Initial `Reporters`
+        pub reporters: Vec<T::AccountId>,
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+        fn build(&self) {
+            let reporters = BoundedVec::<_, T::MaxReporters>::try_from(self.reporters.clone())
+                .expect("Genesis reporters must not exceed `MaxReporters`");
+            Reporters::<T>::put(reporters);
+        }
     }
 
-    #[derive(Debug)]
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
     pub enum DataProcessingError {
         /// Error while reading response data
         RequestReadingError,
@@ -105,9 +187,20 @@ pub mod pallet {
         TransactionError(TransactionSendingError),
         /// Target &str wasn't found in response body
         TargetNotFound,
+        /// Response body doesn't fit into MaxChunks chunks of MaxDataLen
+        BodyTooLarge,
+        /// Response isn't a JSON-RPC 2.0 response
+        InvalidJsonRpcResponse,
+        /// JSON-RPC call failed, contains the error code
+        JsonRpcError(i64),
+        /// JSON pointer of the source doesn't match any part of the JSON-RPC result
+        JsonRpcResultNotFound,
+        /// Payload needs more chunks than are left below MaxChunks, a partially saved
+        /// compressed payload couldn't be decompressed
+        ChunksLimitExceeded,
     }
 
-    #[derive(Debug)]
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
     pub enum HttpRequestError {
         /// Something went wrong when sending http request
         RequestSendingError,
@@ -115,11 +208,15 @@ pub mod pallet {
         RequestBadStatus,
         /// Http response code != 200
         ResponseBadCode,
+        /// Something went wrong when writing http request body
+        RequestWritingError,
+        /// JSON-RPC params of the source aren't a JSON array or object
+        InvalidJsonRpcParams,
     }
 
-    #[derive(Debug)]
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
     pub enum TransactionSendingError {
-        /// No local available account to sign transaction
+        /// No local account of a reporter available to sign transaction
         NoLocalAccountAvailable,
         /// Failed to send transaction
         SendFailed,
@@ -141,31 +238,46 @@ pub mod pallet {
                 return;
             }
 
-            let id = match Self::send_http_request() {
-                Ok(id) => id,
-                Err(e) => {
-                    log::error!("Error while sending http request: {:?}", e);
-                    return;
-                }
+            let mut status = SourceStatus {
+                last_attempt: timestamp().unix_millis(),
+                block_number: block_number.saturated_into(),
+                dry_run: Self::is_dry_run(),
+                ..Default::default()
             };
 
-            if let Err(e) = Self::read_and_save_response_in_chunks(id, block_number) {
-                log::error!("Error while reading or saving http request: {:?}", e);
-                return;
+            if let Err(e) = Self::fetch_and_save(&constants::SOURCE, block_number, &mut status) {
+                status.error = Some(e);
             }
+
+            Self::store_status(constants::SOURCE.url.as_bytes(), &status);
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn try_state(_n: BlockNumberFor<T>) -> Result<(), TryRuntimeError> {
+            Self::do_try_state()
         }
     }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
+        /// Weighs the `MaxChunks` reads of `get_max_k2_or_0` besides the chunk counters,
+        /// the metadata and the chunk itself.
         #[pallet::call_index(0)]
-        #[pallet::weight(10000)]
+        #[pallet::weight(
+            T::DbWeight::get()
+                .reads_writes(<T as Config>::MaxChunks::get().saturating_add(2), 4)
+                .saturating_add(Weight::from_parts(10_000_000, 0))
+        )]
         pub fn save_data_chunk(
             origin: T::RuntimeOrigin,
             data_chunk: Vec<u8>,
             block_number: BlockNumberFor<T>,
+            compression: Compression,
         ) -> DispatchResult {
-            ensure_signed(origin)?;
+            let who = ensure_signed(origin)?;
+            // Only the offchain workers save chunks, anyone else could fix the compression
+            // of a payload before them
+            ensure!(Reporters::<T>::get().contains(&who), Error::<T>::NotReporter);
 
             let bounded_vec: BoundedVec<u8, <T as Config>::MaxDataLen> =
                 data_chunk.try_into().map_err(|_| {
@@ -182,6 +294,15 @@ pub mod pallet {
                 return Err(Error::<T>::ChunksLimitExceeded.into());
             }
 
+            // All chunks of a payload must be saved with the same compression
+            match PayloadMetadata::<T>::get(block_number) {
+                Some(meta) => ensure!(
+                    meta.compression == compression,
+                    Error::<T>::CompressionMismatch
+                ),
+                None => PayloadMetadata::<T>::insert(block_number, PayloadMeta { compression }),
+            }
+
             let k2 = Self::get_max_k2_or_0(block_number);
             let new_k2 = k2.saturating_add(1);
 
@@ -195,6 +316,75 @@ pub mod pallet {
 
             Ok(())
         }
+
+        #[pallet::call_index(1)]
+        #[pallet::weight(
+            T::DbWeight::get()
+                .reads_writes(1, 1)
+                .saturating_add(Weight::from_parts(10_000_000, 0))
+        )]
+        pub fn add_reporter(origin: T::RuntimeOrigin, reporter: T::AccountId) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            Reporters::<T>::try_mutate(|reporters| {
+                ensure!(!reporters.contains(&reporter), Error::<T>::AlreadyReporter);
+                reporters
+                    .try_push(reporter)
+                    .map_err(|_| Error::<T>::TooManyReporters)
+            })?;
+
+            Ok(())
+        }
+
+        #[pallet::call_index(2)]
+        #[pallet::weight(
+            T::DbWeight::get()
+                .reads_writes(1, 1)
+                .saturating_add(Weight::from_parts(10_000_000, 0))
+        )]
+        pub fn remove_reporter(
+            origin: T::RuntimeOrigin,
+            reporter: T::AccountId,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            Reporters::<T>::try_mutate(|reporters| {
+                let index = reporters
+                    .iter()
+                    .position(|r| *r == reporter)
+                    .ok_or(Error::<T>::NotReporter)?;
+                reporters.remove(index);
+                Ok::<_, Error<T>>(())
+            })?;
+
+            Ok(())
+        }
+
+        /// Removes the payload saved for `block_number`, its chunks and its metadata,
+        /// freeing the chunks for new payloads.
+        #[pallet::call_index(3)]
+        #[pallet::weight(
+            T::DbWeight::get()
+                .reads_writes(
+                    <T as Config>::MaxChunks::get().saturating_add(1),
+                    <T as Config>::MaxChunks::get().saturating_add(2),
+                )
+                .saturating_add(Weight::from_parts(10_000_000, 0))
+        )]
+        pub fn clear_payload(
+            origin: T::RuntimeOrigin,
+            block_number: BlockNumberFor<T>,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            // A block number holds at most `MaxChunks` chunks
+            let limit = <T as Config>::MaxChunks::get().saturated_into::<u32>();
+            let removed = DataChunks::<T>::clear_prefix(block_number, limit, None).unique;
+            PayloadMetadata::<T>::remove(block_number);
+            CurrentAmountOfChunks::<T>::mutate(|v| *v = v.saturating_sub(removed.into()));
+
+            Ok(())
+        }
     }
 
     #[pallet::validate_unsigned]
@@ -206,6 +396,7 @@ pub mod pallet {
                 Call::save_data_chunk {
                     data_chunk,
                     block_number,
+                    ..
                 } => ValidTransaction::with_tag_prefix("Data chunk")
                     .and_provides((data_chunk, block_number))
                     .propagate(true)
@@ -216,6 +407,70 @@ pub mod pallet {
     }
 
     impl<T: Config> Pallet<T> {
+        /// Checks the storage invariants of the pallet:
+        /// * `CurrentAmountOfChunks` equals the number of `DataChunks` entries
+        /// * the number of chunks doesn't exceed `MaxChunks`
+        /// * every chunk fits into `MaxDataLen`
+        /// * a block number has `PayloadMetadata` if and only if it has chunks
+        #[cfg(any(feature = "try-runtime", test))]
+        pub fn do_try_state() -> Result<(), TryRuntimeError> {
+            let max_data_len = <T as Config>::MaxDataLen::get() as usize;
+            let mut chunks: u64 = 0;
+
+            for (k1, k2) in DataChunks::<T>::iter_keys() {
+                let len = DataChunks::<T>::decode_len(k1, k2).unwrap_or(0);
+                ensure!(len <= max_data_len, "Chunk is longer than MaxDataLen");
+                chunks = chunks.saturating_add(1);
+            }
+
+            ensure!(
+                chunks == Self::current_amount_of_chunks(),
+                "CurrentAmountOfChunks doesn't match the number of DataChunks entries"
+            );
+            ensure!(
+                chunks <= <T as Config>::MaxChunks::get(),
+                "Number of DataChunks entries exceeds MaxChunks"
+            );
+
+            for block_number in PayloadMetadata::<T>::iter_keys() {
+                ensure!(
+                    DataChunks::<T>::iter_prefix(block_number).next().is_some(),
+                    "PayloadMetadata of a payload without chunks"
+                );
+            }
+            for (block_number, _) in DataChunks::<T>::iter_keys() {
+                ensure!(
+                    PayloadMetadata::<T>::contains_key(block_number),
+                    "Chunks of a payload without PayloadMetadata"
+                );
+            }
+
+            Ok(())
+        }
+
+        /// Payload saved for `block_number`, reassembled from its chunks and decompressed
+        pub fn payload(block_number: BlockNumberFor<T>) -> Option<Vec<u8>> {
+            let mut chunks: Vec<_> = DataChunks::<T>::iter_prefix(block_number).collect();
+            if chunks.is_empty() {
+                return None;
+            }
+            chunks.sort_by_key(|(k2, _)| *k2);
+
+            let data: Vec<u8> = chunks
+                .into_iter()
+                .flat_map(|(_, chunk)| chunk.into_inner())
+                .collect();
+
+            let meta = PayloadMetadata::<T>::get(block_number).unwrap_or_default();
+            meta.compression.decompress(&data, Self::max_payload_len())
+        }
+
+        /// Maximum length of an uncompressed payload
+        fn max_payload_len() -> usize {
+            (<T as Config>::MaxDataLen::get() as usize)
+                .saturating_mul(<T as Config>::MaxChunks::get() as usize)
+        }
+
         /// return current maximal key2 for StorageDoubleMap
         fn get_max_k2_or_0(k1: BlockNumberFor<T>) -> u64 {
             DataChunks::<T>::iter_prefix(k1)
@@ -224,26 +479,70 @@ pub mod pallet {
                 .unwrap_or(0)
         }
 
+        /// Fetches the source and submits its body in chunks, recording the progress in `status`
+        pub(crate) fn fetch_and_save(
+            source: &Source,
+            block_number: BlockNumberFor<T>,
+            status: &mut SourceStatus,
+        ) -> Result<(), WorkerError> {
+            let id = Self::send_http_request(source, status).map_err(|e| {
+                log::error!("Error while sending http request: {:?}", e);
+                WorkerError::Http(e)
+            })?;
+
+            Self::read_and_save_response_in_chunks(source, id, block_number, status).map_err(
+                |e| {
+                    log::error!("Error while reading or saving http request: {:?}", e);
+                    WorkerError::DataProcessing(e)
+                },
+            )
+        }
+
+        /// Whether the node-local dry-run switch is set
+        fn is_dry_run() -> bool {
+            StorageValueRef::persistent(diagnostics::DRY_RUN_KEY)
+                .get::<bool>()
+                .ok()
+                .flatten()
+                .unwrap_or(false)
+        }
+
+        /// Writes `status` of `source` into the offchain local storage
+        fn store_status(source: &[u8], status: &SourceStatus) {
+            let key = diagnostics::status_key(source);
+            StorageValueRef::persistent(&key).set(status);
+        }
+
         fn get_deadline_for(dur: u64) -> Timestamp {
             let now = timestamp();
             let duration = Duration::from_millis(dur);
             now.add(duration)
         }
 
-        fn send_http_request() -> Result<HttpRequestId, HttpRequestError> {
+        fn send_http_request(
+            source: &Source,
+            status: &mut SourceStatus,
+        ) -> Result<HttpRequestId, HttpRequestError> {
             log::info!("Sending request...");
-            let id = http_request_start("GET", constants::URL, &[])
-                .map_err(|_| HttpRequestError::RequestSendingError)?;
-            log::info!("Request was sent successfully, id: {}", id.0);
-
             let response_deadline = Self::get_deadline_for(constants::RESPONSE_TIME_LIMIT);
 
+            let id = match source.kind {
+                SourceKind::Get => http_request_start("GET", source.url, &[])
+                    .map_err(|_| HttpRequestError::RequestSendingError)?,
+                SourceKind::JsonRpc { method, params, .. } => {
+                    let body = json_rpc::request_body(method, params)?;
+                    Self::send_post_request(source.url, &body, response_deadline)?
+                }
+            };
+            log::info!("Request was sent successfully, id: {}", id.0);
+
             log::info!("Waiting for response...");
             let response_status = http_response_wait(&[id], Some(response_deadline));
 
             let response_code = match response_status[0] {
                 HttpRequestStatus::Finished(response_code) => {
                     log::info!("Http response code: {}", response_code);
+                    status.http_code = Some(response_code);
                     response_code
                 }
                 _ => return Err(HttpRequestError::RequestBadStatus),
@@ -256,65 +555,171 @@ pub mod pallet {
             Ok(id)
         }
 
+        /// Starts a POST request with JSON `body`
+        fn send_post_request(
+            url: &str,
+            body: &[u8],
+            deadline: Timestamp,
+        ) -> Result<HttpRequestId, HttpRequestError> {
+            let id = http_request_start("POST", url, &[])
+                .map_err(|_| HttpRequestError::RequestSendingError)?;
+            http_request_add_header(id, "Content-Type", "application/json")
+                .map_err(|_| HttpRequestError::RequestSendingError)?;
+
+            // Empty chunk finalizes the body
+            http_request_write_body(id, body, Some(deadline))
+                .and_then(|_| http_request_write_body(id, &[], Some(deadline)))
+                .map_err(|_| HttpRequestError::RequestWritingError)?;
+
+            Ok(id)
+        }
+
         fn read_and_save_response_in_chunks(
+            source: &Source,
             id: HttpRequestId,
             block_number: BlockNumberFor<T>,
+            status: &mut SourceStatus,
         ) -> Result<(), DataProcessingError> {
+            let body = Self::read_response_body(source, id, status)?;
+            if body.is_empty() {
+                return Ok(());
+            }
+
+            let body = match source.kind {
+                SourceKind::Get => body,
+                SourceKind::JsonRpc { result_pointer, .. } => {
+                    json_rpc::extract_result(&body, result_pointer)?
+                }
+            };
+
+            let payload = source.compression.compress(&body);
+            log::info!(
+                "Body of {} bytes was compressed with {:?} to {} bytes",
+                body.len(),
+                source.compression,
+                payload.len()
+            );
+
+            // The payload is saved completely or not at all
+            let max_data_len = <T as Config>::MaxDataLen::get() as usize;
+            let chunks_left =
+                <T as Config>::MaxChunks::get().saturating_sub(Self::current_amount_of_chunks());
+            if payload.len().div_ceil(max_data_len) as u64 > chunks_left {
+                return Err(DataProcessingError::ChunksLimitExceeded);
+            }
+
+            // Chunks processing
+            for data_chunk in payload.chunks(max_data_len) {
+                if status.dry_run {
+                    log::info!("Dry run, chunk of {} bytes wasn't submitted", data_chunk.len());
+                    continue;
+                }
+
+                let (account, tx_hash) = Self::send_signed_save_transaction(
+                    Vec::from(data_chunk),
+                    block_number,
+                    source.compression,
+                )
+                .map_err(|e| DataProcessingError::TransactionError(e))?;
+                status.chunks_submitted = status.chunks_submitted.saturating_add(1);
+                status.last_tx_hash = Some(tx_hash);
+                log::info!(
+                    "Signed transaction was sent successfully by {:?}",
+                    account.id
+                )
+            }
+
+            Ok(())
+        }
+
+        /// Reads the whole response body, checking every read part for the source target
+        fn read_response_body(
+            source: &Source,
+            id: HttpRequestId,
+            status: &mut SourceStatus,
+        ) -> Result<Vec<u8>, DataProcessingError> {
             let reading_deadline = Self::get_deadline_for(constants::READING_TIME_LIMIT);
 
             let mut buff = vec![0; <T as Config>::MaxDataLen::get() as usize];
+            let mut body = Vec::new();
 
-            // Chunks processing
             loop {
                 log::info!("Reading chunk of body request...");
                 let bytes_to_read = http_response_read_body(id, &mut buff, Some(reading_deadline))
                     .map_err(|_| DataProcessingError::RequestReadingError)?;
 
                 if bytes_to_read == 0 {
-                    return Ok(());
+                    return Ok(body);
                 }
 
                 log::info!(
                     "Chunk was read successfully, bytes to read: {}",
                     bytes_to_read
                 );
+                status.bytes_read = status.bytes_read.saturating_add(bytes_to_read as u64);
 
                 let body_as_u8 = &buff[..bytes_to_read as usize];
                 let body_as_string = String::from_utf8_lossy(body_as_u8);
 
-                if !body_as_string.contains(constants::TARGET) {
+                if !body_as_string.contains(source.target) {
                     return Err(DataProcessingError::TargetNotFound);
                 }
 
-                let data_chunk = Vec::from(body_as_u8);
+                if body.len().saturating_add(body_as_u8.len()) > Self::max_payload_len() {
+                    return Err(DataProcessingError::BodyTooLarge);
+                }
 
-                let account = Self::send_signed_save_transaction(data_chunk, block_number)
-                    .map_err(|e| DataProcessingError::TransactionError(e))?;
-                log::info!(
-                    "Signed transaction was sent successfully by {:?}",
-                    account.id
-                )
+                body.extend_from_slice(body_as_u8);
             }
         }
 
+        /// Signs and submits a `save_data_chunk` transaction, returns the signing account
+        /// and the blake2-256 hash of the submitted extrinsic.
+        ///
+        /// The extrinsic is built here instead of by `Signer::send_signed_transaction`,
+        /// which doesn't expose it, so its hash can be reported.
         fn send_signed_save_transaction(
             data_chunk: Vec<u8>,
             block_number: BlockNumberFor<T>,
-        ) -> Result<Account<T>, TransactionSendingError> {
+            compression: Compression,
+        ) -> Result<(Account<T>, [u8; 32]), TransactionSendingError> {
             let signer = Signer::<T, T::OffChainAuthId>::any_account();
+            let reporters = Reporters::<T>::get();
+            let Some(account) = signer
+                .accounts_from_keys()
+                .find(|account| reporters.contains(&account.id))
+            else {
+                log::error!("No local reporter account available to sign the transaction");
+                return Err(TransactionSendingError::NoLocalAccountAvailable);
+            };
 
             let call = Call::save_data_chunk {
                 data_chunk,
                 block_number,
+                compression,
             };
+            let nonce = frame_system::Pallet::<T>::account_nonce(&account.id);
+            let (call, signature) =
+                <T as CreateSignedTransaction<Call<T>>>::create_transaction::<T::OffChainAuthId>(
+                    call.into(),
+                    account.public.clone(),
+                    account.id.clone(),
+                    nonce,
+                )
+                .ok_or(TransactionSendingError::SendFailed)?;
+            let xt = <T as SendTransactionTypes<Call<T>>>::Extrinsic::new(call, Some(signature))
+                .ok_or(TransactionSendingError::SendFailed)?;
 
-            if let Some((account, res)) = signer.send_signed_transaction(|_account| call.clone()) {
-                res.map_err(|_| TransactionSendingError::SendFailed)?;
-                Ok(account)
-            } else {
-                log::error!("No local account available to sign the transaction");
-                Err(TransactionSendingError::NoLocalAccountAvailable)
-            }
+            let encoded = xt.encode();
+            let tx_hash = sp_io::hashing::blake2_256(&encoded);
+            sp_io::offchain::submit_transaction(encoded)
+                .map_err(|_| TransactionSendingError::SendFailed)?;
+
+            // The next chunk of this run is signed with the next nonce, the offchain worker
+            // never persists this change
+            frame_system::Pallet::<T>::inc_account_nonce(&account.id);
+
+            Ok((account, tx_hash))
         }
     }
 }
@@ -0,0 +1,100 @@
+//! Mock runtime of the hooks pallet.
+
+use crate as hooks_pallet;
+use polkadot_sdk::{
+    frame_support::traits::{ConstU32, ConstU64},
+    frame_system::{
+        offchain::{AppCrypto, CreateSignedTransaction, SendTransactionTypes, SigningTypes},
+        EnsureRoot,
+    },
+    polkadot_sdk_frame::testing_prelude::*,
+    sp_core::sr25519::{Public, Signature},
+    sp_io::TestExternalities,
+    sp_runtime::{
+        testing::TestXt,
+        traits::{Extrinsic as ExtrinsicT, IdentityLookup},
+        BuildStorage,
+    },
+    *,
+};
+
+pub type AccountId = Public;
+pub type Extrinsic = TestXt<RuntimeCall, ()>;
+
+/// `MaxDataLen` of the mock runtime.
+pub const MAX_DATA_LEN: u32 = 64;
+/// `MaxChunks` of the mock runtime.
+pub const MAX_CHUNKS: u64 = 4;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+construct_runtime!(
+    pub enum Test {
+        System: frame_system,
+        HooksPallet: hooks_pallet,
+    }
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+    type Block = Block;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<AccountId>;
+}
+
+impl SigningTypes for Test {
+    type Public = Public;
+    type Signature = Signature;
+}
+
+impl<LocalCall> SendTransactionTypes<LocalCall> for Test
+where
+    RuntimeCall: From<LocalCall>,
+{
+    type OverarchingCall = RuntimeCall;
+    type Extrinsic = Extrinsic;
+}
+
+impl<LocalCall> CreateSignedTransaction<LocalCall> for Test
+where
+    RuntimeCall: From<LocalCall>,
+{
+    fn create_transaction<C: AppCrypto<Self::Public, Self::Signature>>(
+        call: RuntimeCall,
+        _public: Public,
+        _account: AccountId,
+        nonce: u64,
+    ) -> Option<(RuntimeCall, <Extrinsic as ExtrinsicT>::SignaturePayload)> {
+        Some((call, (nonce, ())))
+    }
+}
+
+impl hooks_pallet::Config for Test {
+    type MaxDataLen = ConstU32<MAX_DATA_LEN>;
+    type MaxChunks = ConstU64<MAX_CHUNKS>;
+    type CooldownPeriod = ConstU64<1>;
+    type OffChainAuthId = hooks_pallet::crypto::OffchainAuthId;
+    type MaxReporters = ConstU32<4>;
+    type AdminOrigin = EnsureRoot<AccountId>;
+}
+
+/// Reporter signing `save_data_chunk` in the tests.
+pub fn account() -> AccountId {
+    Public::from_raw([1; 32])
+}
+
+/// Externalities starting at block 1, `account` is the only reporter.
+pub fn new_test_ext() -> TestExternalities {
+    let storage = RuntimeGenesisConfig {
+        system: Default::default(),
+        hooks_pallet: hooks_pallet::GenesisConfig {
+            reporters: vec![account()],
+        },
+    }
+    .build_storage()
+    .unwrap();
+
+    let mut ext = TestExternalities::new(storage);
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
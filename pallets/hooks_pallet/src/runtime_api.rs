@@ -0,0 +1,14 @@
+//! Runtime API of the hooks pallet.
+
+use codec::{alloc::vec::Vec, Codec};
+
+polkadot_sdk::sp_api::decl_runtime_apis! {
+    /// API to read the payloads saved by the offchain worker.
+    pub trait HooksApi<BlockNumber>
+    where
+        BlockNumber: Codec,
+    {
+        /// Payload saved for `block_number`, reassembled from its chunks and decompressed.
+        fn payload(block_number: BlockNumber) -> Option<Vec<u8>>;
+    }
+}
@@ -0,0 +1,42 @@
+//! Sources fetched by the offchain worker.
+
+use crate::compression::Compression;
+
+/// A source fetched by the offchain worker.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Source {
+    /// URL of HTTP request
+    pub url: &'static str,
+    /// How the source is requested
+    pub kind: SourceKind,
+    /// Set the criteria for saving a chunk
+    pub target: &'static str,
+    /// Compression applied to the body before splitting it into chunks
+    pub compression: Compression,
+}
+
+/// How a source is requested and what part of the response is saved.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SourceKind {
+    /// Plain GET request, the whole body is saved
+    Get,
+    /// JSON-RPC 2.0 call sent as a POST request, e.g. to another Substrate or EVM node:
+    ///
+    /// ```ignore
+    /// SourceKind::JsonRpc {
+    ///     method: "chain_getHeader",
+    ///     params: "[]",
+    ///     result_pointer: Some("/number"),
+    /// }
+    /// ```
+    ///
+    /// Only the JSON encoded `result` of the response is saved.
+    JsonRpc {
+        /// Called method
+        method: &'static str,
+        /// JSON encoded params of the call, an array or an object
+        params: &'static str,
+        /// JSON pointer (RFC 6901) to the part of `result` to save, the whole `result` if `None`
+        result_pointer: Option<&'static str>,
+    },
+}
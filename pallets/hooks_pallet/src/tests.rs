@@ -0,0 +1,217 @@
+//! Tests of the hooks pallet.
+
+use crate::{
+    compression::Compression,
+    crypto,
+    diagnostics::SourceStatus,
+    json_rpc,
+    mock::*,
+    source::{Source, SourceKind},
+    CurrentAmountOfChunks, DataChunks, Error, PayloadMetadata, Reporters,
+};
+use codec::Decode;
+use polkadot_sdk::{
+    frame_support::{assert_noop, assert_ok, BoundedVec},
+    sp_core::offchain::{
+        testing::{PendingRequest, TestOffchainExt, TestTransactionPoolExt},
+        OffchainDbExt, OffchainWorkerExt, TransactionPoolExt,
+    },
+    sp_core::sr25519::Public,
+    sp_io::hashing::blake2_256,
+    sp_keystore::{testing::MemoryKeystore, Keystore, KeystoreExt},
+    sp_runtime::{traits::BadOrigin, DispatchResult},
+};
+
+/// JSON-RPC source of the offchain worker tests.
+const JSON_RPC_SOURCE: Source = Source {
+    url: "http://localhost:9944",
+    kind: SourceKind::JsonRpc {
+        method: "chain_getHeader",
+        params: "[]",
+        result_pointer: Some("/number"),
+    },
+    target: "jsonrpc",
+    compression: Compression::None,
+};
+
+fn save(data_chunk: &[u8], block_number: u64) -> DispatchResult {
+    HooksPallet::save_data_chunk(
+        RuntimeOrigin::signed(account()),
+        data_chunk.to_vec(),
+        block_number,
+        Compression::None,
+    )
+}
+
+#[test]
+fn save_data_chunk_works() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(save(b"first", 1));
+        assert_ok!(save(b"second", 1));
+
+        assert_eq!(
+            DataChunks::<Test>::get(1, 1).into_inner(),
+            b"first".to_vec()
+        );
+        assert_eq!(
+            DataChunks::<Test>::get(1, 2).into_inner(),
+            b"second".to_vec()
+        );
+        assert_eq!(CurrentAmountOfChunks::<Test>::get(), 2);
+        assert_eq!(HooksPallet::payload(1), Some(b"firstsecond".to_vec()));
+        assert_ok!(HooksPallet::do_try_state());
+    });
+}
+
+#[test]
+fn save_data_chunk_respects_max_chunks() {
+    new_test_ext().execute_with(|| {
+        for _ in 0..MAX_CHUNKS {
+            assert_ok!(save(b"chunk", 1));
+        }
+
+        assert_noop!(save(b"chunk", 1), Error::<Test>::ChunksLimitExceeded);
+    });
+}
+
+#[test]
+fn save_data_chunk_requires_reporter() {
+    new_test_ext().execute_with(|| {
+        let other = Public::from_raw([2; 32]);
+        let save_as = |who| {
+            HooksPallet::save_data_chunk(
+                RuntimeOrigin::signed(who),
+                b"chunk".to_vec(),
+                1,
+                Compression::Deflate,
+            )
+        };
+
+        // Can't fix the compression of the payload before the reporter
+        assert_noop!(save_as(other), Error::<Test>::NotReporter);
+        assert_ok!(save(b"chunk", 1));
+
+        assert_noop!(
+            HooksPallet::add_reporter(RuntimeOrigin::signed(other), other),
+            BadOrigin
+        );
+        assert_ok!(HooksPallet::add_reporter(RuntimeOrigin::root(), other));
+        assert_noop!(
+            HooksPallet::add_reporter(RuntimeOrigin::root(), other),
+            Error::<Test>::AlreadyReporter
+        );
+        assert_noop!(save_as(other), Error::<Test>::CompressionMismatch);
+
+        assert_ok!(HooksPallet::remove_reporter(
+            RuntimeOrigin::root(),
+            account()
+        ));
+        assert_eq!(Reporters::<Test>::get().into_inner(), vec![other]);
+        assert_noop!(save(b"chunk", 1), Error::<Test>::NotReporter);
+    });
+}
+
+#[test]
+fn clear_payload_removes_chunks_and_metadata() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(save(b"first", 1));
+        assert_ok!(save(b"second", 1));
+        assert_ok!(save(b"other", 2));
+
+        assert_noop!(
+            HooksPallet::clear_payload(RuntimeOrigin::signed(account()), 1),
+            BadOrigin
+        );
+        assert_ok!(HooksPallet::clear_payload(RuntimeOrigin::root(), 1));
+
+        assert_eq!(HooksPallet::payload(1), None);
+        assert!(!PayloadMetadata::<Test>::contains_key(1));
+        assert_eq!(CurrentAmountOfChunks::<Test>::get(), 1);
+        assert_eq!(HooksPallet::payload(2), Some(b"other".to_vec()));
+        assert_ok!(HooksPallet::do_try_state());
+
+        // A new payload of the block number can use another compression
+        assert_ok!(HooksPallet::save_data_chunk(
+            RuntimeOrigin::signed(account()),
+            b"chunk".to_vec(),
+            1,
+            Compression::Deflate,
+        ));
+    });
+}
+
+#[test]
+fn try_state_detects_wrong_chunk_count() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(save(b"chunk", 1));
+
+        CurrentAmountOfChunks::<Test>::put(2);
+
+        assert!(HooksPallet::do_try_state().is_err());
+    });
+}
+
+#[test]
+fn try_state_detects_too_many_chunks() {
+    new_test_ext().execute_with(|| {
+        for k2 in 1..=MAX_CHUNKS + 1 {
+            DataChunks::<Test>::insert(1, k2, BoundedVec::truncate_from(b"chunk".to_vec()));
+        }
+        CurrentAmountOfChunks::<Test>::put(MAX_CHUNKS + 1);
+
+        assert!(HooksPallet::do_try_state().is_err());
+    });
+}
+
+#[test]
+fn offchain_worker_saves_json_rpc_result() {
+    let (offchain, offchain_state) = TestOffchainExt::new();
+    let (pool, pool_state) = TestTransactionPoolExt::new();
+    let keystore = MemoryKeystore::new();
+    let reporter = keystore
+        .sr25519_generate_new(crypto::KEY_TYPE, None)
+        .unwrap();
+
+    let mut ext = new_test_ext();
+    ext.register_extension(OffchainWorkerExt::new(offchain.clone()));
+    ext.register_extension(OffchainDbExt::new(offchain));
+    ext.register_extension(TransactionPoolExt::new(pool));
+    ext.register_extension(KeystoreExt::new(keystore));
+
+    offchain_state.write().expect_request(PendingRequest {
+        method: "POST".into(),
+        uri: JSON_RPC_SOURCE.url.into(),
+        headers: vec![("Content-Type".into(), "application/json".into())],
+        body: json_rpc::request_body("chain_getHeader", "[]").unwrap(),
+        response: Some(br#"{"jsonrpc":"2.0","id":1,"result":{"number":"0x10"}}"#.to_vec()),
+        sent: true,
+        ..Default::default()
+    });
+
+    ext.execute_with(|| {
+        assert_ok!(HooksPallet::add_reporter(RuntimeOrigin::root(), reporter));
+        let mut status = SourceStatus::default();
+        assert_ok!(HooksPallet::fetch_and_save(
+            &JSON_RPC_SOURCE,
+            1,
+            &mut status
+        ));
+
+        assert_eq!(status.http_code, Some(200));
+        assert_eq!(status.chunks_submitted, 1);
+
+        let tx = pool_state.write().transactions.pop().unwrap();
+        assert!(pool_state.read().transactions.is_empty());
+        assert_eq!(status.last_tx_hash, Some(blake2_256(&tx)));
+        let tx = Extrinsic::decode(&mut &*tx).unwrap();
+        assert_eq!(tx.signature.map(|(nonce, _)| nonce), Some(0));
+        assert_eq!(
+            tx.call,
+            RuntimeCall::HooksPallet(crate::Call::save_data_chunk {
+                data_chunk: br#""0x10""#.to_vec(),
+                block_number: 1,
+                compression: Compression::None,
+            })
+        );
+    });
+}
@@ -7,27 +7,74 @@
 
 use polkadot_sdk::{
     frame_support::{
-        traits::{Currency, Get},
+        traits::{fungible, fungibles, Get},
         PalletId,
     },
     frame_system, polkadot_sdk_frame as frame,
 };
 
 use frame::traits::AccountIdConversion;
+
+pub mod migrations;
+pub mod runtime_api;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
 // Re-export all pallet parts, this is needed to properly import the pallet into the runtime.
 pub use my_pallet::*;
 
-type BalanceOf<T> =
-    <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+type BalanceOf<T> = <<T as Config>::Currency as fungible::Inspect<
+    <T as frame_system::Config>::AccountId,
+>>::Balance;
 
 #[frame::pallet]
 pub mod my_pallet {
     use super::*;
 
-    use frame::{prelude::*, traits::ExistenceRequirement};
+    use codec::alloc::vec::Vec;
+    use frame::prelude::*;
+    use polkadot_sdk::frame_support::storage::{with_storage_layer, PrefixIterator};
+    use polkadot_sdk::frame_support::traits::{
+        fungible::{Inspect, Mutate},
+        fungibles::{Inspect as _, Mutate as _},
+        tokens::{Fortitude, Preservation},
+        Currency, VestedTransfer, VestingSchedule,
+    };
     use polkadot_sdk::{
-        sp_arithmetic::traits::Saturating, sp_runtime::transaction_validity::InvalidTransaction,
+        sp_arithmetic::traits::Saturating,
+        sp_core::{H256, U256},
+        sp_io::hashing::blake2_256,
+        sp_runtime::{
+            traits::{IdentifyAccount, One, Verify, Zero},
+            transaction_validity::InvalidTransaction,
+            SaturatedConversion,
+        },
     };
+    #[cfg(any(feature = "try-runtime", test))]
+    use polkadot_sdk::sp_runtime::TryRuntimeError;
+
+    /// Number of leading zero bits required in the proof of work hash of `token_faucet`,
+    /// `0` disables the proof of work.
+    #[pallet::storage]
+    pub type PowDifficulty<T: Config> = StorageValue<_, u8, ValueQuery>;
+
+    /// Accounts of the off-chain faucet services allowed to sign vouchers.
+    #[pallet::storage]
+    pub type Operators<T: Config> =
+        StorageValue<_, BoundedVec<T::AccountId, T::MaxOperators>, ValueQuery>;
+
+    /// nonce : expiry of the claimed voucher, a voucher nonce can only be claimed once.
+    /// Entries past their expiry are cleared in `on_idle`.
+    #[pallet::storage]
+    pub type UsedVoucherNonces<T: Config> =
+        StorageMap<_, Twox64Concat, u64, BlockNumberFor<T>, OptionQuery>;
+
+    /// Total amount minted by `set_balance`.
+    #[pallet::storage]
+    pub type TotalMinted<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
 
     #[pallet::storage]
     pub type LastRequests<T: Config> = StorageMap<
@@ -38,9 +85,128 @@ pub mod my_pallet {
         ValueQuery,
     >;
 
+    /// Raw key of `LastRequests` the expired entries are cleared from in `on_idle`,
+    /// `None` to start from the beginning.
+    #[pallet::storage]
+    pub type LastRequestsCursor<T: Config> = StorageValue<_, SweepCursor, OptionQuery>;
+
+    /// Raw key of `UsedVoucherNonces` the expired entries are cleared from in `on_idle`,
+    /// `None` to start from the beginning.
+    #[pallet::storage]
+    pub type UsedVoucherNoncesCursor<T: Config> = StorageValue<_, SweepCursor, OptionQuery>;
+
+    /// Raw key of `AssetLastRequests` the expired entries are cleared from in `on_idle`,
+    /// `None` to start from the beginning.
+    #[pallet::storage]
+    pub type AssetLastRequestsCursor<T: Config> = StorageValue<_, SweepCursor, OptionQuery>;
+
+    /// asset id : faucet configuration of the asset, assets without one can't be requested.
+    #[pallet::storage]
+    pub type AssetConfigs<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AssetId,
+        AssetFaucetConfig<BalanceOf<T>, BlockNumberFor<T>>,
+        OptionQuery,
+    >;
+
+    /// asset : budget of the asset, assets without one have an unlimited budget.
+    #[pallet::storage]
+    pub type Budgets<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        AssetKind<T::AssetId>,
+        BudgetConfig<BalanceOf<T>, BlockNumberFor<T>>,
+        OptionQuery,
+    >;
+
+    /// asset : (budget spent in the current period, start of the period)
+    #[pallet::storage]
+    pub type BudgetUsage<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        AssetKind<T::AssetId>,
+        (BalanceOf<T>, BlockNumberFor<T>),
+        ValueQuery,
+    >;
+
+    /// (k1: asset, k2: destination) : total ever received, including queued drips
+    #[pallet::storage]
+    pub type LifetimeReceived<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        AssetKind<T::AssetId>,
+        Blake2_128Concat,
+        T::AccountId,
+        BalanceOf<T>,
+        ValueQuery,
+    >;
+
+    /// Drips waiting for budget or queued in queue mode, paid in order in `on_initialize`.
+    #[pallet::storage]
+    pub type QueuedDrips<T: Config> = StorageValue<
+        _,
+        BoundedVec<
+            QueuedDrip<T::AssetId, T::AccountId, BalanceOf<T>, BlockNumberFor<T>>,
+            T::MaxQueueLen,
+        >,
+        ValueQuery,
+    >;
+
+    /// blake2-256 hash of the code : claim code, kept after it was redeemed.
+    #[pallet::storage]
+    pub type ClaimCodes<T: Config> = StorageMap<
+        _,
+        Identity,
+        H256,
+        ClaimCode<BalanceOf<T>, BlockNumberFor<T>>,
+        OptionQuery,
+    >;
+
+    /// Automatic top-up of the native faucet balance, checked in `on_initialize`.
+    #[pallet::storage]
+    pub type TopUp<T: Config> = StorageValue<_, TopUpConfig<BalanceOf<T>>, OptionQuery>;
+
+    /// Whether all drips are queued and paid in `on_initialize` instead of immediately.
+    #[pallet::storage]
+    pub type QueueMode<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+    /// Which destinations can request funds with `token_faucet`.
+    #[pallet::storage]
+    pub type Mode<T: Config> = StorageValue<_, AccessMode, ValueQuery>;
+
+    /// Destinations allowed to request funds in `AccessMode::AllowlistOnly`.
+    #[pallet::storage]
+    pub type Allowlist<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+    /// Destinations never allowed to request funds, regardless of the mode.
+    #[pallet::storage]
+    pub type Denylist<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+    /// Amount of blocks native `token_faucet` drips are linearly vested over, drips are
+    /// transferred at once if `None`. Queued drips, vouchers and claim codes are never vested.
+    #[pallet::storage]
+    pub type NativeVestingPeriod<T: Config> = StorageValue<_, BlockNumberFor<T>, OptionQuery>;
+
+    /// (k1: asset id, k2: destination) : LastRequests of the asset
+    #[pallet::storage]
+    pub type AssetLastRequests<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AssetId,
+        Blake2_128Concat,
+        T::AccountId,
+        (BalanceOf<T>, BlockNumberFor<T>),
+        ValueQuery,
+    >;
+
     #[pallet::config]
     pub trait Config: polkadot_sdk::frame_system::Config {
-        type Currency: Currency<Self::AccountId>;
+        type RuntimeEvent: From<Event<Self>>
+            + IsType<<Self as polkadot_sdk::frame_system::Config>::RuntimeEvent>;
+
+        type Currency: fungible::Inspect<Self::AccountId, Balance: MaybeSerializeDeserialize>
+            + fungible::Mutate<Self::AccountId>;
 
         #[pallet::constant]
         type AccumulationPeriod: Get<BlockNumberFor<Self>>;
@@ -50,6 +216,232 @@ pub mod my_pallet {
 
         #[pallet::constant]
         type PalletId: Get<PalletId>;
+
+        /// Origin allowed to mint balance with `set_balance`.
+        type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Maximum amount minted by a single `set_balance` call, unlimited if `None`.
+        #[pallet::constant]
+        type MaxMintPerCall: Get<Option<BalanceOf<Self>>>;
+
+        /// Maximum amount minted by `set_balance` in total, unlimited if `None`.
+        #[pallet::constant]
+        type MaxTotalMint: Get<Option<BalanceOf<Self>>>;
+
+        /// Maximum age of the block a proof of work is bound to.
+        #[pallet::constant]
+        type PowMaxAge: Get<BlockNumberFor<Self>>;
+
+        /// Signature of the vouchers, verified against the operator accounts.
+        type OperatorSignature: Parameter
+            + Verify<Signer: IdentifyAccount<AccountId = Self::AccountId>>;
+
+        /// Maximum amount of operators.
+        #[pallet::constant]
+        type MaxOperators: Get<u32>;
+
+        /// Identifier of the assets dripped besides the native token.
+        type AssetId: Parameter + MaxEncodedLen + Copy;
+
+        /// Assets dripped besides the native token.
+        type Assets: fungibles::Inspect<
+                Self::AccountId,
+                AssetId = Self::AssetId,
+                Balance = BalanceOf<Self>,
+            > + fungibles::Mutate<Self::AccountId>;
+
+        /// Maximum amount of queued drips.
+        #[pallet::constant]
+        type MaxQueueLen: Get<u32>;
+
+        /// Maximum amount of queued drips paid or expired in a block.
+        #[pallet::constant]
+        type MaxPayoutsPerBlock: Get<u32>;
+
+        /// Amount of blocks a queued drip waits to be paid before it expires.
+        #[pallet::constant]
+        type QueuedDripLifetime: Get<BlockNumberFor<Self>>;
+
+        /// Maximum amount of accounts added or removed by a single list update.
+        #[pallet::constant]
+        type MaxListUpdate: Get<u32>;
+
+        /// Vesting schedules of the native `token_faucet` drips while a `NativeVestingPeriod`
+        /// is set, e.g. `pallet-vesting`.
+        ///
+        /// The `Currency` bound comes from `VestedTransfer` and `VestingSchedule` themselves,
+        /// the deprecated `Currency` trait is only used to name the vesting balance, the faucet
+        /// moves funds with the fungible traits.
+        type Vesting: VestedTransfer<
+                Self::AccountId,
+                Moment = BlockNumberFor<Self>,
+                Currency: Currency<Self::AccountId, Balance = BalanceOf<Self>>,
+            > + VestingSchedule<
+                Self::AccountId,
+                Moment = BlockNumberFor<Self>,
+                Currency: Currency<Self::AccountId, Balance = BalanceOf<Self>>,
+            >;
+
+        /// Minimum amount of a vested drip, the `MinVestedTransfer` of `Vesting`.
+        #[pallet::constant]
+        type MinVestedTransfer: Get<BalanceOf<Self>>;
+
+        /// Maximum amount of entries of each map checked for expiry in `on_idle`.
+        #[pallet::constant]
+        type MaxRequestsCheckedPerBlock: Get<u32>;
+
+        /// Maximum amount of claim codes created by a single `create_claim_codes` call.
+        #[pallet::constant]
+        type MaxClaimCodesPerCall: Get<u32>;
+
+        /// Account the `TopUpSource::Reserve` top-ups are transferred from, e.g. the pot
+        /// account of a treasury. Reserve top-ups can't be set if `None`.
+        ///
+        /// Funds of this account can be moved to the faucet by `AdminOrigin`.
+        #[pallet::constant]
+        type TopUpReserve: Get<Option<Self::AccountId>>;
+    }
+
+    /// Maximum length of a claim code redeemed with `redeem`.
+    pub const MAX_CLAIM_CODE_LEN: u32 = 64;
+
+    /// Maximum length of the raw map keys stored in the `on_idle` cursors.
+    pub const MAX_CURSOR_LEN: u32 = 128;
+
+    /// Raw key of a map the `on_idle` cleanup continues from.
+    pub type SweepCursor = BoundedVec<u8, ConstU32<MAX_CURSOR_LEN>>;
+
+    /// Hand-estimated computation of a call besides its storage accesses.
+    pub const CALL_BASE_WEIGHT: Weight = Weight::from_parts(10_000_000, 0);
+
+    /// Hand-estimated computation of checking an operator signature.
+    pub const SIGNATURE_CHECK_WEIGHT: Weight = Weight::from_parts(50_000_000, 0);
+
+    /// Weight of a call doing `reads` storage reads and `writes` storage writes.
+    ///
+    /// The calls aren't benchmarked, their storage accesses are counted by hand
+    /// and include the account and vesting entries of the fungible transfers.
+    pub fn call_weight<T: Config>(reads: u64, writes: u64) -> Weight {
+        T::DbWeight::get()
+            .reads_writes(reads, writes)
+            .saturating_add(CALL_BASE_WEIGHT)
+    }
+
+    /// Which destinations can request funds with `token_faucet`.
+    #[derive(
+        Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Copy, PartialEq, Eq, RuntimeDebug, Default,
+    )]
+    pub enum AccessMode {
+        /// Everyone except the denylisted destinations
+        #[default]
+        Open,
+        /// Only allowlisted destinations which aren't denylisted
+        AllowlistOnly,
+    }
+
+    /// Access list maintained by the admin origin.
+    #[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+    pub enum AccessList {
+        Allowlist,
+        Denylist,
+    }
+
+    /// What happens to a drip exceeding the budget of the current period.
+    #[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+    pub enum ExhaustedBehaviour {
+        /// The request fails with `BudgetExhausted`
+        Reject,
+        /// The request is queued and paid once the budget allows
+        Queue,
+    }
+
+    /// Global budget of an asset.
+    #[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+    pub struct BudgetConfig<Balance, BlockNumber> {
+        /// Maximum amount dripped to all destinations within `period`
+        pub budget: Balance,
+        /// Length of the budget period in blocks
+        pub period: BlockNumber,
+        /// Maximum amount a destination can ever receive, unlimited if `None`
+        pub lifetime_cap: Option<Balance>,
+        /// What happens to drips exceeding `budget`
+        pub on_exhausted: ExhaustedBehaviour,
+    }
+
+    /// Drip waiting for budget, or for its turn in queue mode.
+    #[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+    pub struct QueuedDrip<AssetId, AccountId, Balance, BlockNumber> {
+        pub asset: AssetKind<AssetId>,
+        pub dest: AccountId,
+        pub amount: Balance,
+        /// Block the drip is dropped at if it wasn't paid yet
+        pub expires_at: BlockNumber,
+    }
+
+    /// Claim code paying `amount` of the native token once, until `expiry`.
+    #[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+    pub struct ClaimCode<Balance, BlockNumber> {
+        pub amount: Balance,
+        /// Last block the code can be redeemed at
+        pub expiry: BlockNumber,
+        /// Whether the code was redeemed
+        pub used: bool,
+    }
+
+    /// Where the automatic top-ups of the native faucet balance come from.
+    #[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+    pub enum TopUpSource {
+        /// Transferred from the `TopUpReserve` account, which is kept alive
+        Reserve,
+        /// Minted, counted in `TotalMinted` and capped by `MaxTotalMint`
+        Mint,
+    }
+
+    /// Automatic top-up of the native faucet balance.
+    #[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+    pub struct TopUpConfig<Balance> {
+        /// Faucet balance below which the faucet is topped up
+        pub low_water_mark: Balance,
+        /// Amount added by a top-up
+        pub amount: Balance,
+        /// Where the top-ups come from
+        pub source: TopUpSource,
+    }
+
+    /// Token requested from the faucet.
+    #[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+    pub enum AssetKind<AssetId> {
+        /// Native token, limited by `FaucetAmount` and `AccumulationPeriod`
+        Native,
+        /// Asset limited by its `AssetConfigs` entry
+        Asset(AssetId),
+    }
+
+    /// Faucet configuration of an asset.
+    #[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+    pub struct AssetFaucetConfig<Balance, BlockNumber> {
+        /// Maximum amount received by a destination within `accumulation_period`
+        pub max_drip: Balance,
+        /// Length of the window in blocks
+        pub accumulation_period: BlockNumber,
+        /// Whether the asset can be requested
+        pub enabled: bool,
+    }
+
+    /// Context prepended to the signed voucher payload.
+    pub const VOUCHER_CONTEXT: &[u8] = b"faucet-voucher";
+
+    /// Proof of work attached to `token_faucet`.
+    ///
+    /// It's valid if `blake2_256(SCALE(dest, asset, block_hash, amount, nonce))`, where
+    /// `block_hash` is the hash of the recent block `block_number`, has at least `PowDifficulty`
+    /// leading zero bits. A proof is bound to the requested asset, it can't be reused for another.
+    #[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+    pub struct ProofOfWork<BlockNumber> {
+        /// Block whose hash the proof is bound to
+        pub block_number: BlockNumber,
+        /// Nonce found by the requester
+        pub nonce: u64,
     }
 
     impl<T: Config> Pallet<T> {
@@ -57,6 +449,804 @@ pub mod my_pallet {
         pub fn account_id() -> T::AccountId {
             T::PalletId::get().into_account_truncating()
         }
+
+        /// Proof of work hash of a `token_faucet` request, see [`ProofOfWork`].
+        pub fn pow_hash(
+            dest: &T::AccountId,
+            asset: &AssetKind<T::AssetId>,
+            block_hash: &T::Hash,
+            amount: &BalanceOf<T>,
+            nonce: u64,
+        ) -> [u8; 32] {
+            (dest, asset, block_hash, amount, nonce).using_encoded(blake2_256)
+        }
+
+        /// Payload an operator signs to authorize a `claim_voucher`, i.e. the SCALE encoded
+        /// `(VOUCHER_CONTEXT, genesis_hash, dest, amount, expiry, nonce)`.
+        pub fn voucher_payload(
+            dest: &T::AccountId,
+            amount: &BalanceOf<T>,
+            expiry: &BlockNumberFor<T>,
+            nonce: u64,
+        ) -> Vec<u8> {
+            let genesis_hash = frame_system::Pallet::<T>::block_hash(BlockNumberFor::<T>::zero());
+            (VOUCHER_CONTEXT, genesis_hash, dest, amount, expiry, nonce).encode()
+        }
+
+        /// Checks that the voucher is unexpired, unused and signed by an operator,
+        /// returns the operator who signed it.
+        fn check_voucher(
+            dest: &T::AccountId,
+            amount: &BalanceOf<T>,
+            expiry: &BlockNumberFor<T>,
+            nonce: u64,
+            signature: &T::OperatorSignature,
+        ) -> Result<T::AccountId, Error<T>> {
+            let now = frame_system::Pallet::<T>::block_number();
+            ensure!(now <= *expiry, Error::<T>::VoucherExpired);
+            ensure!(
+                !UsedVoucherNonces::<T>::contains_key(nonce),
+                Error::<T>::VoucherAlreadyUsed
+            );
+
+            let payload = Self::voucher_payload(dest, amount, expiry, nonce);
+            Operators::<T>::get()
+                .into_iter()
+                .find(|operator| signature.verify(&payload[..], operator))
+                .ok_or(Error::<T>::InvalidVoucherSignature)
+        }
+
+        /// Checks that `code` is a known, unused and unexpired claim code,
+        /// returns its hash and the claim code.
+        fn check_claim_code(
+            code: &[u8],
+        ) -> Result<(H256, ClaimCode<BalanceOf<T>, BlockNumberFor<T>>), Error<T>> {
+            let hash = H256(blake2_256(code));
+            let claim_code = ClaimCodes::<T>::get(hash).ok_or(Error::<T>::UnknownClaimCode)?;
+
+            ensure!(!claim_code.used, Error::<T>::ClaimCodeUsed);
+            let now = frame_system::Pallet::<T>::block_number();
+            ensure!(now <= claim_code.expiry, Error::<T>::ClaimCodeExpired);
+
+            Ok((hash, claim_code))
+        }
+
+        /// Balance of `asset` in the faucet account available for drips,
+        /// excluding held and frozen funds.
+        pub fn faucet_balance(asset: &AssetKind<T::AssetId>) -> BalanceOf<T> {
+            let account_id = Self::account_id();
+            match asset {
+                AssetKind::Native => T::Currency::reducible_balance(
+                    &account_id,
+                    Preservation::Expendable,
+                    Fortitude::Polite,
+                ),
+                AssetKind::Asset(id) => T::Assets::reducible_balance(
+                    *id,
+                    &account_id,
+                    Preservation::Expendable,
+                    Fortitude::Polite,
+                ),
+            }
+        }
+
+        /// Maximum amount of `asset` received by a destination within a window,
+        /// and the length of the window.
+        fn limits(
+            asset: &AssetKind<T::AssetId>,
+        ) -> Result<(BalanceOf<T>, BlockNumberFor<T>), Error<T>> {
+            match asset {
+                AssetKind::Native => Ok((T::FaucetAmount::get(), T::AccumulationPeriod::get())),
+                AssetKind::Asset(id) => AssetConfigs::<T>::get(id)
+                    .filter(|config| config.enabled)
+                    .map(|config| (config.max_drip, config.accumulation_period))
+                    .ok_or(Error::<T>::AssetNotEnabled),
+            }
+        }
+
+        fn last_request(
+            asset: &AssetKind<T::AssetId>,
+            dest: &T::AccountId,
+        ) -> (BalanceOf<T>, BlockNumberFor<T>) {
+            match asset {
+                AssetKind::Native => LastRequests::<T>::get(dest),
+                AssetKind::Asset(id) => AssetLastRequests::<T>::get(id, dest),
+            }
+        }
+
+        fn set_last_request(
+            asset: &AssetKind<T::AssetId>,
+            dest: &T::AccountId,
+            request: (BalanceOf<T>, BlockNumberFor<T>),
+        ) {
+            match asset {
+                AssetKind::Native => LastRequests::<T>::insert(dest, request),
+                AssetKind::Asset(id) => AssetLastRequests::<T>::insert(id, dest, request),
+            }
+        }
+
+        /// Amount of `asset` `who` can still request within the current window, capped by the
+        /// lifetime cap of the asset, and the block the window resets at.
+        /// `None` if the asset is not enabled.
+        pub fn allowance(
+            asset: &AssetKind<T::AssetId>,
+            who: &T::AccountId,
+        ) -> Option<(BalanceOf<T>, BlockNumberFor<T>)> {
+            let (max_drip, accumulation_period) = Self::limits(asset).ok()?;
+
+            let (balance, last_time) = Self::last_request(asset, who);
+            let now = frame_system::Pallet::<T>::block_number();
+
+            let (remaining, reset_at) = if now.saturating_sub(last_time) >= accumulation_period {
+                (max_drip, now)
+            } else {
+                (
+                    max_drip.saturating_sub(balance),
+                    last_time.saturating_add(accumulation_period),
+                )
+            };
+
+            let remaining = match Budgets::<T>::get(asset).and_then(|config| config.lifetime_cap) {
+                Some(cap) => {
+                    let received = LifetimeReceived::<T>::get(asset, who);
+                    remaining.min(cap.saturating_sub(received))
+                }
+                None => remaining,
+            };
+
+            Some((remaining, reset_at))
+        }
+
+        /// Sends `amount` of `asset` from the faucet account to `dest`,
+        /// within the window limits of `dest`. The drip is vested if `vest` and it's paid
+        /// immediately.
+        fn drip(
+            asset: AssetKind<T::AssetId>,
+            dest: T::AccountId,
+            amount: BalanceOf<T>,
+            vest: bool,
+        ) -> DispatchResult {
+            let (total, now) = Self::window_total(&asset, &dest, amount)?;
+            let received = Self::lifetime_total(&asset, &dest, amount)?;
+            let schedule = if vest {
+                Self::vesting_schedule(&asset, &dest, amount)?
+            } else {
+                None
+            };
+
+            let queue_mode = QueueMode::<T>::get();
+            if !queue_mode && Self::try_spend_budget(&asset, amount) {
+                Self::transfer_from_faucet(&asset, &dest, amount, schedule)?;
+
+                Self::deposit_event(Event::Dripped {
+                    asset,
+                    dest: dest.clone(),
+                    amount,
+                    window_total: total,
+                });
+            } else {
+                if !queue_mode {
+                    let on_exhausted = Budgets::<T>::get(asset).map(|config| config.on_exhausted);
+                    ensure!(
+                        on_exhausted == Some(ExhaustedBehaviour::Queue),
+                        Error::<T>::BudgetExhausted
+                    );
+                }
+
+                let expires_at = frame_system::Pallet::<T>::block_number()
+                    .saturating_add(T::QueuedDripLifetime::get());
+                QueuedDrips::<T>::try_append(QueuedDrip {
+                    asset,
+                    dest: dest.clone(),
+                    amount,
+                    expires_at,
+                })
+                .map_err(|_| Error::<T>::QueueFull)?;
+
+                Self::deposit_event(Event::DripQueued {
+                    asset,
+                    dest: dest.clone(),
+                    amount,
+                });
+            }
+
+            Self::set_last_request(&asset, &dest, (total, now));
+            LifetimeReceived::<T>::insert(asset, &dest, received);
+
+            Ok(())
+        }
+
+        /// Vesting schedule `(per_block, starting_block)` of a `token_faucet` drip,
+        /// `None` if the drip isn't vested. Fails if `Vesting` wouldn't accept the schedule.
+        pub fn vesting_schedule(
+            asset: &AssetKind<T::AssetId>,
+            dest: &T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> Result<Option<(BalanceOf<T>, BlockNumberFor<T>)>, Error<T>> {
+            let AssetKind::Native = asset else {
+                return Ok(None);
+            };
+            let Some(period) = NativeVestingPeriod::<T>::get().filter(|period| !period.is_zero())
+            else {
+                return Ok(None);
+            };
+
+            ensure!(
+                amount >= T::MinVestedTransfer::get(),
+                Error::<T>::VestedAmountTooLow
+            );
+
+            let blocks: BalanceOf<T> = period.saturated_into::<u128>().saturated_into();
+            let per_block = (amount / blocks).max(One::one());
+            let now = frame_system::Pallet::<T>::block_number();
+
+            T::Vesting::can_add_vesting_schedule(dest, amount, per_block, now)
+                .map_err(|_| Error::<T>::TooManyVestingSchedules)?;
+
+            Ok(Some((per_block, now)))
+        }
+
+        /// Transfers `amount` of `asset` from the faucet account to `dest`, the native token
+        /// under the vesting `schedule` if set.
+        fn transfer_from_faucet(
+            asset: &AssetKind<T::AssetId>,
+            dest: &T::AccountId,
+            amount: BalanceOf<T>,
+            schedule: Option<(BalanceOf<T>, BlockNumberFor<T>)>,
+        ) -> DispatchResult {
+            let account_id = Self::account_id();
+            let (max_drip, _) = Self::limits(asset)?;
+            let faucet_balance = Self::faucet_balance(asset);
+
+            ensure!(faucet_balance >= amount, Error::<T>::NotEnoughFaucetBalance);
+
+            // The faucet account may be drained completely
+            match asset {
+                AssetKind::Native => match schedule {
+                    Some((per_block, start)) => {
+                        T::Vesting::vested_transfer(&account_id, dest, amount, per_block, start)?;
+                    }
+                    None => {
+                        T::Currency::transfer(&account_id, dest, amount, Preservation::Expendable)?;
+                    }
+                },
+                AssetKind::Asset(id) => {
+                    T::Assets::transfer(*id, &account_id, dest, amount, Preservation::Expendable)?;
+                }
+            }
+
+            // Only the drip taking the balance below the maximum drip reports it
+            let balance = Self::faucet_balance(asset);
+            if faucet_balance >= max_drip && balance < max_drip {
+                Self::deposit_event(Event::FaucetDry {
+                    asset: *asset,
+                    balance,
+                });
+            }
+
+            Ok(())
+        }
+
+        /// Total of `asset` ever received by `dest` after receiving `amount`.
+        fn lifetime_total(
+            asset: &AssetKind<T::AssetId>,
+            dest: &T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> Result<BalanceOf<T>, Error<T>> {
+            let received = LifetimeReceived::<T>::get(asset, dest).saturating_add(amount);
+
+            if let Some(cap) = Budgets::<T>::get(asset).and_then(|config| config.lifetime_cap) {
+                ensure!(received <= cap, Error::<T>::LifetimeCapExceeded);
+            }
+
+            Ok(received)
+        }
+
+        /// Budget config of `asset`, the budget spent in the current period and the start of
+        /// the period, `None` if the budget is unlimited.
+        fn budget_usage(
+            asset: &AssetKind<T::AssetId>,
+        ) -> Option<(
+            BudgetConfig<BalanceOf<T>, BlockNumberFor<T>>,
+            BalanceOf<T>,
+            BlockNumberFor<T>,
+        )> {
+            let config = Budgets::<T>::get(asset)?;
+            let (spent, start) = BudgetUsage::<T>::get(asset);
+            let now = frame_system::Pallet::<T>::block_number();
+
+            if now.saturating_sub(start) >= config.period {
+                Some((config, Zero::zero(), now))
+            } else {
+                Some((config, spent, start))
+            }
+        }
+
+        /// Budget of `asset` remaining in the current period, `None` if unlimited.
+        pub fn remaining_budget(asset: &AssetKind<T::AssetId>) -> Option<BalanceOf<T>> {
+            Self::budget_usage(asset).map(|(config, spent, _)| config.budget.saturating_sub(spent))
+        }
+
+        /// Spends `amount` of the `asset` budget if the budget allows it,
+        /// returns whether it was spent.
+        fn try_spend_budget(asset: &AssetKind<T::AssetId>, amount: BalanceOf<T>) -> bool {
+            let Some((config, spent, start)) = Self::budget_usage(asset) else {
+                return true;
+            };
+
+            let spent = spent.saturating_add(amount);
+            if spent > config.budget {
+                return false;
+            }
+
+            BudgetUsage::<T>::insert(asset, (spent, start));
+            true
+        }
+
+        /// Pays queued drips in order, as long as the budget and the faucet balance allow it,
+        /// and drops the expired ones.
+        ///
+        /// The queue is strictly first in, first out: a head drip the budget or the balance of
+        /// its asset doesn't allow blocks the drips behind it, of any asset, until it is paid or
+        /// expires after `QueuedDripLifetime`.
+        fn process_queue() -> Weight {
+            let mut queue = QueuedDrips::<T>::get();
+            let mut weight = T::DbWeight::get().reads(1);
+            if queue.is_empty() {
+                return weight;
+            }
+
+            let now = frame_system::Pallet::<T>::block_number();
+            let mut processed: u32 = 0;
+            while processed < T::MaxPayoutsPerBlock::get() {
+                let Some(drip) = queue.first().cloned() else {
+                    break;
+                };
+                weight.saturating_accrue(T::DbWeight::get().reads(3));
+
+                if drip.expires_at <= now {
+                    queue.remove(0);
+                    processed = processed.saturating_add(1);
+                    weight.saturating_accrue(T::DbWeight::get().writes(2));
+
+                    Self::refund_request(&drip.asset, &drip.dest, drip.amount);
+                    Self::deposit_event(Event::QueuedDripExpired {
+                        asset: drip.asset,
+                        dest: drip.dest,
+                        amount: drip.amount,
+                    });
+                    continue;
+                }
+
+                // Keep the order, the head waits until the budget and the balance allow it
+                let budget_allows = Self::remaining_budget(&drip.asset)
+                    .is_none_or(|remaining| remaining >= drip.amount);
+                if !budget_allows || Self::faucet_balance(&drip.asset) < drip.amount {
+                    break;
+                }
+
+                queue.remove(0);
+                processed = processed.saturating_add(1);
+                weight.saturating_accrue(T::DbWeight::get().writes(3));
+
+                // A failed payout must not leave a partial transfer behind
+                let result = with_storage_layer(|| {
+                    Self::transfer_from_faucet(&drip.asset, &drip.dest, drip.amount, None)
+                });
+                match result {
+                    Ok(()) => {
+                        Self::try_spend_budget(&drip.asset, drip.amount);
+                        Self::deposit_event(Event::QueuedDripPaid {
+                            asset: drip.asset,
+                            dest: drip.dest,
+                            amount: drip.amount,
+                        });
+                    }
+                    Err(error) => {
+                        Self::refund_request(&drip.asset, &drip.dest, drip.amount);
+                        Self::deposit_event(Event::QueuedDripFailed {
+                            asset: drip.asset,
+                            dest: drip.dest,
+                            amount: drip.amount,
+                            error,
+                        });
+                    }
+                }
+            }
+
+            QueuedDrips::<T>::put(queue);
+            weight.saturating_add(T::DbWeight::get().writes(1))
+        }
+
+        /// Tops up the native faucet balance from the `TopUp` source if it is below
+        /// the low-water mark.
+        fn top_up() -> Weight {
+            let db = T::DbWeight::get();
+            let Some(config) = TopUp::<T>::get() else {
+                return db.reads(1);
+            };
+
+            let balance = Self::faucet_balance(&AssetKind::Native);
+            if balance >= config.low_water_mark {
+                return db.reads(2);
+            }
+
+            let account_id = Self::account_id();
+            let result = match &config.source {
+                TopUpSource::Reserve => match T::TopUpReserve::get() {
+                    Some(reserve) => {
+                        let available = T::Currency::reducible_balance(
+                            &reserve,
+                            Preservation::Preserve,
+                            Fortitude::Polite,
+                        );
+                        let amount = config.amount.min(available);
+                        T::Currency::transfer(&reserve, &account_id, amount, Preservation::Preserve)
+                            .map(|_| amount)
+                    }
+                    None => Err(Error::<T>::NoTopUpReserve.into()),
+                },
+                TopUpSource::Mint => {
+                    let total_minted = TotalMinted::<T>::get();
+                    let amount = match T::MaxTotalMint::get() {
+                        Some(cap) => config.amount.min(cap.saturating_sub(total_minted)),
+                        None => config.amount,
+                    };
+                    T::Currency::mint_into(&account_id, amount).map(|amount| {
+                        TotalMinted::<T>::put(total_minted.saturating_add(amount));
+                        amount
+                    })
+                }
+            };
+
+            match result {
+                Ok(amount) if !amount.is_zero() => Self::deposit_event(Event::ToppedUp {
+                    source: config.source,
+                    amount,
+                }),
+                Ok(_) => log::warn!("Faucet top-up source is exhausted"),
+                Err(e) => log::error!("Failed to top up the faucet: {:?}", e),
+            }
+
+            db.reads_writes(4, 3)
+        }
+
+        /// Gives `amount` of an unpaid drip back to the window and the lifetime total of `dest`.
+        fn refund_request(
+            asset: &AssetKind<T::AssetId>,
+            dest: &T::AccountId,
+            amount: BalanceOf<T>,
+        ) {
+            let (total, start) = Self::last_request(asset, dest);
+            Self::set_last_request(asset, dest, (total.saturating_sub(amount), start));
+            LifetimeReceived::<T>::mutate(asset, dest, |received| {
+                *received = received.saturating_sub(amount)
+            });
+        }
+
+        /// Position of the first queued drip of `who`, `0` being paid next.
+        pub fn queue_position(who: &T::AccountId) -> Option<u32> {
+            QueuedDrips::<T>::get()
+                .iter()
+                .position(|drip| &drip.dest == who)
+                .map(|position| position as u32)
+        }
+
+        /// Checks that `dest` may request funds in the current `Mode`.
+        pub fn ensure_allowed(dest: &T::AccountId) -> Result<(), Error<T>> {
+            ensure!(!Denylist::<T>::contains_key(dest), Error::<T>::Denied);
+
+            if Mode::<T>::get() == AccessMode::AllowlistOnly {
+                ensure!(
+                    Allowlist::<T>::contains_key(dest),
+                    Error::<T>::NotAllowlisted
+                );
+            }
+
+            Ok(())
+        }
+
+        /// Removes the `LastRequests` and `AssetLastRequests` entries whose window has expired
+        /// and the `UsedVoucherNonces` entries past their expiry, each map continuing from its
+        /// cursor, using at most `limit` weight in total.
+        /// A removed entry is equivalent to an expired one, so requests and vouchers aren't
+        /// affected. The windows of an asset without config are removed as well.
+        fn clear_expired_entries(limit: Weight) -> Weight {
+            let now = frame_system::Pallet::<T>::block_number();
+            let accumulation_period = T::AccumulationPeriod::get();
+
+            let (mut weight, cursor) = Self::sweep(
+                |cursor| match cursor {
+                    Some(cursor) => LastRequests::<T>::iter_from(cursor),
+                    None => LastRequests::<T>::iter(),
+                },
+                LastRequestsCursor::<T>::get(),
+                |(who, (_, last_time))| {
+                    (now.saturating_sub(last_time) >= accumulation_period).then_some(who)
+                },
+                LastRequests::<T>::remove,
+                T::DbWeight::get().reads_writes(1, 1),
+                limit,
+            );
+            LastRequestsCursor::<T>::set(cursor);
+
+            let (assets_weight, cursor) = Self::sweep(
+                |cursor| match cursor {
+                    Some(cursor) => AssetLastRequests::<T>::iter_from(cursor),
+                    None => AssetLastRequests::<T>::iter(),
+                },
+                AssetLastRequestsCursor::<T>::get(),
+                |(asset_id, who, (_, last_time))| {
+                    let expired = AssetConfigs::<T>::get(asset_id).is_none_or(|config| {
+                        now.saturating_sub(last_time) >= config.accumulation_period
+                    });
+                    expired.then_some((asset_id, who))
+                },
+                |(asset_id, who)| AssetLastRequests::<T>::remove(asset_id, who),
+                T::DbWeight::get().reads_writes(2, 1),
+                limit.saturating_sub(weight),
+            );
+            AssetLastRequestsCursor::<T>::set(cursor);
+            weight.saturating_accrue(assets_weight);
+
+            // Expired vouchers fail before their nonce is checked
+            let (nonces_weight, cursor) = Self::sweep(
+                |cursor| match cursor {
+                    Some(cursor) => UsedVoucherNonces::<T>::iter_from(cursor),
+                    None => UsedVoucherNonces::<T>::iter(),
+                },
+                UsedVoucherNoncesCursor::<T>::get(),
+                |(nonce, expiry)| (now > expiry).then_some(nonce),
+                UsedVoucherNonces::<T>::remove,
+                T::DbWeight::get().reads_writes(1, 1),
+                limit.saturating_sub(weight),
+            );
+            UsedVoucherNoncesCursor::<T>::set(cursor);
+
+            weight.saturating_accrue(nonces_weight);
+            weight
+        }
+
+        /// Removes the entries of a map for which `expired` returns a key, checking at most
+        /// `MaxRequestsCheckedPerBlock` entries from `cursor` within `limit` weight, each
+        /// costing `per_entry`.
+        /// `iter` iterates the map from a raw key, or from its start if `None`.
+        ///
+        /// Returns the used weight, including the read and write of the cursor, and the
+        /// cursor to continue from, `None` to start over once the whole map was checked.
+        fn sweep<I, K>(
+            iter: impl FnOnce(Option<Vec<u8>>) -> PrefixIterator<I>,
+            cursor: Option<SweepCursor>,
+            expired: impl Fn(I) -> Option<K>,
+            remove: impl Fn(K),
+            per_entry: Weight,
+            limit: Weight,
+        ) -> (Weight, Option<SweepCursor>) {
+            let mut weight = T::DbWeight::get().reads_writes(1, 1);
+            if weight.saturating_add(per_entry).any_gt(limit) {
+                return (Weight::zero(), cursor);
+            }
+
+            let mut iter = iter(cursor.map(BoundedVec::into_inner));
+
+            let mut keys = Vec::new();
+            let mut checked: u32 = 0;
+            let mut finished = false;
+            while checked < T::MaxRequestsCheckedPerBlock::get()
+                && weight.saturating_add(per_entry).all_lte(limit)
+            {
+                let Some(item) = iter.next() else {
+                    finished = true;
+                    break;
+                };
+
+                keys.extend(expired(item));
+                checked = checked.saturating_add(1);
+                weight.saturating_accrue(per_entry);
+            }
+
+            let cursor = if finished {
+                None
+            } else {
+                BoundedVec::try_from(iter.last_raw_key().to_vec()).ok()
+            };
+
+            // The map must not be altered while iterating it
+            for key in keys {
+                remove(key);
+            }
+
+            (weight, cursor)
+        }
+
+        /// Total of `asset` received by `dest` within its window after receiving `amount`,
+        /// and the start of that window.
+        fn window_total(
+            asset: &AssetKind<T::AssetId>,
+            dest: &T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> Result<(BalanceOf<T>, BlockNumberFor<T>), Error<T>> {
+            let (max_drip, accumulation_period) = Self::limits(asset)?;
+            ensure!(amount <= max_drip, Error::<T>::AmountTooHigh);
+
+            let (balance, last_time) = Self::last_request(asset, dest);
+            let now = frame_system::Pallet::<T>::block_number();
+            let period = now.saturating_sub(last_time);
+
+            let (total, now) = if period >= accumulation_period {
+                (amount, now)
+            } else {
+                (balance.saturating_add(amount), last_time)
+            };
+
+            ensure!(total <= max_drip, Error::<T>::RequestLimitExceeded);
+
+            Ok((total, now))
+        }
+
+        /// Checks the proof of work of a `token_faucet` request against `PowDifficulty`,
+        /// returns for how many blocks the proof stays valid.
+        fn check_pow(
+            dest: &T::AccountId,
+            asset: &AssetKind<T::AssetId>,
+            amount: &BalanceOf<T>,
+            pow: &Option<ProofOfWork<BlockNumberFor<T>>>,
+        ) -> Result<BlockNumberFor<T>, InvalidTransaction> {
+            let difficulty = PowDifficulty::<T>::get();
+            if difficulty == 0 {
+                return Ok(T::PowMaxAge::get());
+            }
+
+            let pow = pow.as_ref().ok_or(InvalidTransaction::BadProof)?;
+
+            let now = frame_system::Pallet::<T>::block_number();
+            ensure!(pow.block_number < now, InvalidTransaction::Future);
+            let age = now.saturating_sub(pow.block_number);
+            ensure!(age <= T::PowMaxAge::get(), InvalidTransaction::Stale);
+
+            let block_hash = frame_system::Pallet::<T>::block_hash(pow.block_number);
+            ensure!(block_hash != T::Hash::default(), InvalidTransaction::Stale);
+
+            let hash = Self::pow_hash(dest, asset, &block_hash, amount, pow.nonce);
+            ensure!(
+                U256::from_big_endian(&hash).leading_zeros() >= difficulty as u32,
+                InvalidTransaction::BadProof
+            );
+
+            Ok(T::PowMaxAge::get().saturating_sub(age))
+        }
+
+        /// Checks that no destination has received more than `FaucetAmount` within its window.
+        ///
+        /// The asset windows aren't checked, as `set_asset_config` may lower the maximum drip of
+        /// a window already received.
+        #[cfg(any(feature = "try-runtime", test))]
+        pub fn do_try_state() -> Result<(), TryRuntimeError> {
+            for (total, _) in LastRequests::<T>::iter_values() {
+                ensure!(
+                    total <= T::FaucetAmount::get(),
+                    "LastRequests total exceeds FaucetAmount"
+                );
+            }
+
+            for (asset, (spent, _)) in BudgetUsage::<T>::iter() {
+                if let Some(config) = Budgets::<T>::get(asset) {
+                    ensure!(spent <= config.budget, "BudgetUsage exceeds the budget");
+                }
+            }
+
+            Ok(())
+        }
+    }
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// `amount` of `asset` was sent to `dest`, who received `window_total` within
+        /// the current window.
+        Dripped {
+            asset: AssetKind<T::AssetId>,
+            dest: T::AccountId,
+            amount: BalanceOf<T>,
+            window_total: BalanceOf<T>,
+        },
+        /// The faucet account was refilled with `amount` by `from`.
+        Refilled {
+            from: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+        /// `amount` was minted into the account of `who`.
+        BalanceSet {
+            who: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+        /// The faucet balance of `asset` dropped below its maximum drip,
+        /// full drips will fail until a refill.
+        FaucetDry {
+            asset: AssetKind<T::AssetId>,
+            balance: BalanceOf<T>,
+        },
+        /// Proof of work difficulty of `token_faucet` was set to `difficulty`.
+        PowDifficultySet { difficulty: u8 },
+        /// `operator` was allowed to sign vouchers.
+        OperatorAdded { operator: T::AccountId },
+        /// `operator` was disallowed to sign vouchers.
+        OperatorRemoved { operator: T::AccountId },
+        /// Voucher `nonce` signed by `operator` was claimed for `dest`.
+        VoucherClaimed {
+            operator: T::AccountId,
+            dest: T::AccountId,
+            nonce: u64,
+        },
+        /// `amount` of `asset` for `dest` was queued, the budget of the period is exhausted.
+        DripQueued {
+            asset: AssetKind<T::AssetId>,
+            dest: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+        /// Queued drip of `amount` of `asset` was paid to `dest`.
+        QueuedDripPaid {
+            asset: AssetKind<T::AssetId>,
+            dest: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+        /// Queued drip of `amount` of `asset` for `dest` couldn't be paid and was dropped,
+        /// e.g. because the asset was disabled while the drip was queued.
+        QueuedDripFailed {
+            asset: AssetKind<T::AssetId>,
+            dest: T::AccountId,
+            amount: BalanceOf<T>,
+            error: DispatchError,
+        },
+        /// Queued drip of `amount` of `asset` for `dest` expired before it was paid.
+        QueuedDripExpired {
+            asset: AssetKind<T::AssetId>,
+            dest: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+        /// `count` claim codes paying `amount` until `expiry` were created.
+        ClaimCodesCreated {
+            count: u32,
+            amount: BalanceOf<T>,
+            expiry: BlockNumberFor<T>,
+        },
+        /// Claim code `hash` was redeemed, paying `amount` to `dest`.
+        ClaimCodeRedeemed {
+            hash: H256,
+            dest: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+        /// The native faucet balance was topped up with `amount` from `source`.
+        ToppedUp {
+            source: TopUpSource,
+            amount: BalanceOf<T>,
+        },
+        /// Automatic top-up was set, or disabled if `None`.
+        TopUpSet {
+            config: Option<TopUpConfig<BalanceOf<T>>>,
+        },
+        /// Queue mode was enabled or disabled.
+        QueueModeSet { enabled: bool },
+        /// Budget of `asset` was set, or removed if `None`.
+        BudgetSet {
+            asset: AssetKind<T::AssetId>,
+            config: Option<BudgetConfig<BalanceOf<T>, BlockNumberFor<T>>>,
+        },
+        /// Access mode of `token_faucet` was set to `mode`.
+        AccessModeSet { mode: AccessMode },
+        /// `added` accounts were added to and `removed` accounts were removed from `list`.
+        AccessListUpdated {
+            list: AccessList,
+            added: u32,
+            removed: u32,
+        },
+        /// Vesting period of the native drips was set to `period`, or removed if `None`.
+        NativeVestingPeriodSet { period: Option<BlockNumberFor<T>> },
+        /// Faucet configuration of `asset_id` was set, or removed if `None`.
+        AssetConfigSet {
+            asset_id: T::AssetId,
+            config: Option<AssetFaucetConfig<BalanceOf<T>, BlockNumberFor<T>>>,
+        },
     }
 
     #[pallet::error]
@@ -64,76 +1254,462 @@ pub mod my_pallet {
         AmountTooHigh,
         RequestLimitExceeded,
         NotEnoughFaucetBalance,
+        /// `set_balance` amount is higher than `MaxMintPerCall`.
+        MintPerCallCapExceeded,
+        /// `set_balance` would mint more than `MaxTotalMint` in total.
+        TotalMintCapExceeded,
+        /// Operators limit `MaxOperators` exceeded.
+        TooManyOperators,
+        /// Account is already an operator.
+        AlreadyOperator,
+        /// Account isn't an operator.
+        NotOperator,
+        /// Voucher expiry block has passed.
+        VoucherExpired,
+        /// Voucher nonce was already claimed.
+        VoucherAlreadyUsed,
+        /// Voucher isn't signed by any operator.
+        InvalidVoucherSignature,
+        /// Asset has no faucet configuration or is disabled.
+        AssetNotEnabled,
+        /// Global budget of the current period is exhausted.
+        BudgetExhausted,
+        /// Destination would exceed the lifetime cap of the asset.
+        LifetimeCapExceeded,
+        /// Queue of drips waiting for budget is full.
+        QueueFull,
+        /// Destination is denylisted.
+        Denied,
+        /// Destination isn't allowlisted and the faucet is allowlist-only.
+        NotAllowlisted,
+        /// Claim code with the same hash already exists.
+        ClaimCodeExists,
+        /// Claim code doesn't exist.
+        UnknownClaimCode,
+        /// Claim code was already redeemed.
+        ClaimCodeUsed,
+        /// Claim code expiry block has passed.
+        ClaimCodeExpired,
+        /// Claim codes would pay nothing.
+        ZeroClaimCodeAmount,
+        /// Reserve top-ups were set while `TopUpReserve` is `None`.
+        NoTopUpReserve,
+        /// Vested drip is lower than `MinVestedTransfer`.
+        VestedAmountTooLow,
+        /// Destination can't get another vesting schedule, e.g. it has the maximum amount.
+        TooManyVestingSchedules,
+    }
+
+    #[pallet::genesis_config]
+    #[derive(DefaultNoBound)]
+    pub struct GenesisConfig<T: Config> {
+        /// Amount minted into the faucet account
+        pub balance: BalanceOf<T>,
+        /// Whether the faucet starts in `AccessMode::AllowlistOnly`
+        pub allowlist_only: bool,
+        /// Initial `Allowlist`
+        pub allowlist: Vec<T::AccountId>,
+        /// Initial `Denylist`
+        pub denylist: Vec<T::AccountId>,
+        /// Initial `PowDifficulty`
+        pub pow_difficulty: u8,
+        /// Initial `Operators`
+        pub operators: Vec<T::AccountId>,
+        /// Initial `NativeVestingPeriod`
+        pub native_vesting_period: Option<BlockNumberFor<T>>,
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+        fn build(&self) {
+            if !self.balance.is_zero() {
+                T::Currency::mint_into(&Pallet::<T>::account_id(), self.balance)
+                    .expect("Faucet account must be funded at genesis");
+            }
+
+            if self.allowlist_only {
+                Mode::<T>::put(AccessMode::AllowlistOnly);
+            }
+            for who in &self.allowlist {
+                Allowlist::<T>::insert(who, ());
+            }
+            for who in &self.denylist {
+                Denylist::<T>::insert(who, ());
+            }
+
+            PowDifficulty::<T>::put(self.pow_difficulty);
+
+            let operators = BoundedVec::<_, T::MaxOperators>::try_from(self.operators.clone())
+                .expect("Genesis operators must not exceed `MaxOperators`");
+            Operators::<T>::put(operators);
+
+            NativeVestingPeriod::<T>::set(self.native_vesting_period);
+        }
     }
 
+    /// The in-code storage version, see [`crate::migrations`].
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+            Self::top_up().saturating_add(Self::process_queue())
+        }
+
+        fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            Self::clear_expired_entries(remaining_weight)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn try_state(_n: BlockNumberFor<T>) -> Result<(), TryRuntimeError> {
+            Self::do_try_state()
+        }
+    }
+
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         #[pallet::call_index(0)]
-        #[pallet::weight(10000)]
+        #[pallet::weight(call_weight::<T>(12, 7))]
         pub fn token_faucet(
             origin: T::RuntimeOrigin,
             dest: T::AccountId,
+            asset: AssetKind<T::AssetId>,
             amount: BalanceOf<T>,
+            pow: Option<ProofOfWork<BlockNumberFor<T>>>,
         ) -> DispatchResult {
             ensure_none(origin)?;
-            ensure!(amount <= T::FaucetAmount::get(), Error::<T>::AmountTooHigh);
+            // The proof of work is checked in `validate_unsigned`
+            let _ = pow;
 
-            let (balance, last_time) = LastRequests::<T>::get(&dest);
-            let now = frame_system::Pallet::<T>::block_number();
-            let period = now.saturating_sub(last_time);
-
-            let (total, now) = if period >= T::AccumulationPeriod::get() {
-                (amount, now)
-            } else {
-                (balance + amount, last_time)
-            };
+            Self::ensure_allowed(&dest)?;
 
-            ensure!(
-                total <= T::FaucetAmount::get(),
-                Error::<T>::RequestLimitExceeded
-            );
-
-            let account_id = Self::account_id();
-            let faucet_balance = T::Currency::free_balance(&account_id);
-
-            ensure!(faucet_balance >= amount, Error::<T>::NotEnoughFaucetBalance);
-
-            T::Currency::transfer(&account_id, &dest, amount, ExistenceRequirement::AllowDeath)?;
-
-            LastRequests::<T>::insert(&dest, (total, now));
-            Ok(())
+            Self::drip(asset, dest, amount, true)
         }
 
         #[pallet::call_index(1)]
-        #[pallet::weight(10000)]
+        #[pallet::weight(call_weight::<T>(2, 2))]
         pub fn refill_pallet(origin: T::RuntimeOrigin, amount: BalanceOf<T>) -> DispatchResult {
             let sender = ensure_signed(origin)?;
 
             let account_id = Self::account_id();
 
-            T::Currency::transfer(
-                &sender,
-                &account_id,
+            // The refilling account is kept alive
+            T::Currency::transfer(&sender, &account_id, amount, Preservation::Preserve)?;
+
+            Self::deposit_event(Event::Refilled {
+                from: sender,
                 amount,
-                ExistenceRequirement::KeepAlive,
-            )?;
+            });
 
             Ok(())
         }
 
         #[pallet::call_index(2)]
-        #[pallet::weight(10000)]
+        #[pallet::weight(call_weight::<T>(3, 3))]
         pub fn set_balance(
             origin: T::RuntimeOrigin,
             who: T::AccountId,
             amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            if let Some(cap) = T::MaxMintPerCall::get() {
+                ensure!(amount <= cap, Error::<T>::MintPerCallCapExceeded);
+            }
+
+            let total_minted = TotalMinted::<T>::get().saturating_add(amount);
+            if let Some(cap) = T::MaxTotalMint::get() {
+                ensure!(total_minted <= cap, Error::<T>::TotalMintCapExceeded);
+            }
+
+            T::Currency::mint_into(&who, amount)?;
+            TotalMinted::<T>::put(total_minted);
+
+            Self::deposit_event(Event::BalanceSet { who, amount });
+
+            Ok(())
+        }
+
+        #[pallet::call_index(3)]
+        #[pallet::weight(call_weight::<T>(0, 1))]
+        pub fn set_pow_difficulty(origin: T::RuntimeOrigin, difficulty: u8) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            PowDifficulty::<T>::put(difficulty);
+
+            Self::deposit_event(Event::PowDifficultySet { difficulty });
+
+            Ok(())
+        }
+
+        #[pallet::call_index(4)]
+        #[pallet::weight(call_weight::<T>(12, 7).saturating_add(SIGNATURE_CHECK_WEIGHT))]
+        pub fn claim_voucher(
+            origin: T::RuntimeOrigin,
+            dest: T::AccountId,
+            amount: BalanceOf<T>,
+            expiry: BlockNumberFor<T>,
+            nonce: u64,
+            signature: T::OperatorSignature,
+        ) -> DispatchResult {
+            ensure_none(origin)?;
+
+            let operator = Self::check_voucher(&dest, &amount, &expiry, nonce, &signature)?;
+
+            Self::drip(AssetKind::Native, dest.clone(), amount, false)?;
+
+            UsedVoucherNonces::<T>::insert(nonce, expiry);
+
+            Self::deposit_event(Event::VoucherClaimed {
+                operator,
+                dest,
+                nonce,
+            });
+
+            Ok(())
+        }
+
+        #[pallet::call_index(5)]
+        #[pallet::weight(call_weight::<T>(1, 1))]
+        pub fn add_operator(origin: T::RuntimeOrigin, operator: T::AccountId) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            Operators::<T>::try_mutate(|operators| {
+                ensure!(!operators.contains(&operator), Error::<T>::AlreadyOperator);
+                operators
+                    .try_push(operator.clone())
+                    .map_err(|_| Error::<T>::TooManyOperators)
+            })?;
+
+            Self::deposit_event(Event::OperatorAdded { operator });
+
+            Ok(())
+        }
+
+        #[pallet::call_index(6)]
+        #[pallet::weight(call_weight::<T>(1, 1))]
+        pub fn remove_operator(
+            origin: T::RuntimeOrigin,
+            operator: T::AccountId,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            Operators::<T>::try_mutate(|operators| {
+                let index = operators
+                    .iter()
+                    .position(|o| *o == operator)
+                    .ok_or(Error::<T>::NotOperator)?;
+                operators.remove(index);
+                Ok::<_, Error<T>>(())
+            })?;
+
+            Self::deposit_event(Event::OperatorRemoved { operator });
+
+            Ok(())
+        }
+
+        #[pallet::call_index(7)]
+        #[pallet::weight(call_weight::<T>(0, 1))]
+        pub fn set_asset_config(
+            origin: T::RuntimeOrigin,
+            asset_id: T::AssetId,
+            config: Option<AssetFaucetConfig<BalanceOf<T>, BlockNumberFor<T>>>,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            AssetConfigs::<T>::set(asset_id, config.clone());
+
+            Self::deposit_event(Event::AssetConfigSet { asset_id, config });
+
+            Ok(())
+        }
+
+        #[pallet::call_index(8)]
+        #[pallet::weight(call_weight::<T>(1, 2))]
+        pub fn set_budget(
+            origin: T::RuntimeOrigin,
+            asset: AssetKind<T::AssetId>,
+            config: Option<BudgetConfig<BalanceOf<T>, BlockNumberFor<T>>>,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            // A lowered budget counts as spent if the period already spent more
+            match &config {
+                Some(config) => BudgetUsage::<T>::mutate(asset, |(spent, _)| {
+                    *spent = (*spent).min(config.budget);
+                }),
+                None => BudgetUsage::<T>::remove(asset),
+            }
+            Budgets::<T>::set(asset, config.clone());
+
+            Self::deposit_event(Event::BudgetSet { asset, config });
+
+            Ok(())
+        }
+
+        #[pallet::call_index(9)]
+        #[pallet::weight(call_weight::<T>(0, 1))]
+        pub fn set_access_mode(origin: T::RuntimeOrigin, mode: AccessMode) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            Mode::<T>::put(mode);
+
+            Self::deposit_event(Event::AccessModeSet { mode });
+
+            Ok(())
+        }
+
+        #[pallet::call_index(10)]
+        #[pallet::weight(call_weight::<T>(
+            0,
+            (add.len() as u64).saturating_add(remove.len() as u64)
+        ))]
+        pub fn update_access_list(
+            origin: T::RuntimeOrigin,
+            list: AccessList,
+            add: BoundedVec<T::AccountId, T::MaxListUpdate>,
+            remove: BoundedVec<T::AccountId, T::MaxListUpdate>,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            for who in add.iter() {
+                match list {
+                    AccessList::Allowlist => Allowlist::<T>::insert(who, ()),
+                    AccessList::Denylist => Denylist::<T>::insert(who, ()),
+                }
+            }
+            for who in remove.iter() {
+                match list {
+                    AccessList::Allowlist => Allowlist::<T>::remove(who),
+                    AccessList::Denylist => Denylist::<T>::remove(who),
+                }
+            }
+
+            Self::deposit_event(Event::AccessListUpdated {
+                list,
+                added: add.len() as u32,
+                removed: remove.len() as u32,
+            });
+
+            Ok(())
+        }
+
+        #[pallet::call_index(11)]
+        #[pallet::weight(call_weight::<T>(0, 1))]
+        pub fn set_native_vesting_period(
+            origin: T::RuntimeOrigin,
+            period: Option<BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            NativeVestingPeriod::<T>::set(period);
+
+            Self::deposit_event(Event::NativeVestingPeriodSet { period });
+
+            Ok(())
+        }
+
+        #[pallet::call_index(12)]
+        #[pallet::weight(call_weight::<T>(0, 1))]
+        pub fn set_queue_mode(origin: T::RuntimeOrigin, enabled: bool) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            QueueMode::<T>::put(enabled);
+
+            Self::deposit_event(Event::QueueModeSet { enabled });
+
+            Ok(())
+        }
+
+        #[pallet::call_index(13)]
+        #[pallet::weight(call_weight::<T>(0, 1))]
+        pub fn set_top_up(
+            origin: T::RuntimeOrigin,
+            config: Option<TopUpConfig<BalanceOf<T>>>,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+            if let Some(TopUpConfig {
+                source: TopUpSource::Reserve,
+                ..
+            }) = config
+            {
+                ensure!(T::TopUpReserve::get().is_some(), Error::<T>::NoTopUpReserve);
+            }
+
+            TopUp::<T>::set(config.clone());
+
+            Self::deposit_event(Event::TopUpSet { config });
+
+            Ok(())
+        }
+
+        /// Creates claim codes from the blake2-256 `hashes` of the codes handed out,
+        /// each paying `amount` once until `expiry`.
+        #[pallet::call_index(14)]
+        #[pallet::weight(call_weight::<T>(hashes.len() as u64, hashes.len() as u64))]
+        pub fn create_claim_codes(
+            origin: T::RuntimeOrigin,
+            hashes: BoundedVec<H256, T::MaxClaimCodesPerCall>,
+            amount: BalanceOf<T>,
+            expiry: BlockNumberFor<T>,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+            ensure!(!amount.is_zero(), Error::<T>::ZeroClaimCodeAmount);
+            ensure!(
+                expiry >= frame_system::Pallet::<T>::block_number(),
+                Error::<T>::ClaimCodeExpired
+            );
+
+            for hash in hashes.iter() {
+                ensure!(
+                    !ClaimCodes::<T>::contains_key(hash),
+                    Error::<T>::ClaimCodeExists
+                );
+                ClaimCodes::<T>::insert(
+                    hash,
+                    ClaimCode {
+                        amount,
+                        expiry,
+                        used: false,
+                    },
+                );
+            }
+
+            Self::deposit_event(Event::ClaimCodesCreated {
+                count: hashes.len() as u32,
+                amount,
+                expiry,
+            });
+
+            Ok(())
+        }
+
+        /// Redeems claim `code`, paying its amount from the faucet account to `dest`.
+        ///
+        /// The code is public once the transaction is in the pool, hand codes out
+        /// to people who redeem them right away.
+        #[pallet::call_index(15)]
+        #[pallet::weight(call_weight::<T>(3, 3))]
+        pub fn redeem(
+            origin: T::RuntimeOrigin,
+            code: BoundedVec<u8, ConstU32<MAX_CLAIM_CODE_LEN>>,
+            dest: T::AccountId,
         ) -> DispatchResult {
             ensure_none(origin)?;
 
-            T::Currency::deposit_into_existing(&who, amount)?;
+            let (hash, mut claim_code) = Self::check_claim_code(&code)?;
+
+            Self::transfer_from_faucet(&AssetKind::Native, &dest, claim_code.amount, None)?;
+
+            let amount = claim_code.amount;
+            claim_code.used = true;
+            ClaimCodes::<T>::insert(hash, claim_code);
+
+            Self::deposit_event(Event::ClaimCodeRedeemed { hash, dest, amount });
 
             Ok(())
         }
@@ -145,14 +1721,85 @@ pub mod my_pallet {
 
         fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
             match call {
-                Call::token_faucet { dest, amount } => ValidTransaction::with_tag_prefix("Faucet")
-                    .and_provides((dest, amount))
-                    .propagate(true)
-                    .build(),
-                Call::set_balance { who, amount } => ValidTransaction::with_tag_prefix("Faucet")
-                    .and_provides((who, amount))
-                    .propagate(true)
-                    .build(),
+                Call::token_faucet {
+                    dest,
+                    asset,
+                    amount,
+                    pow,
+                } => {
+                    Self::ensure_allowed(dest).map_err(|_| InvalidTransaction::BadSigner)?;
+                    Self::window_total(asset, dest, *amount).map_err(|e| match e {
+                        Error::<T>::AmountTooHigh | Error::<T>::AssetNotEnabled => {
+                            InvalidTransaction::Call
+                        }
+                        _ => InvalidTransaction::ExhaustsResources,
+                    })?;
+                    Self::lifetime_total(asset, dest, *amount)
+                        .map_err(|_| InvalidTransaction::Call)?;
+                    Self::vesting_schedule(asset, dest, *amount).map_err(|e| match e {
+                        Error::<T>::VestedAmountTooLow => InvalidTransaction::Call,
+                        _ => InvalidTransaction::ExhaustsResources,
+                    })?;
+                    let longevity = Self::check_pow(dest, asset, amount, pow)?;
+
+                    ValidTransaction::with_tag_prefix("Faucet")
+                        .and_provides((dest, asset, amount))
+                        .longevity(longevity.saturated_into::<u64>().max(1))
+                        .propagate(true)
+                        .build()
+                }
+                Call::claim_voucher {
+                    dest,
+                    amount,
+                    expiry,
+                    nonce,
+                    signature,
+                } => {
+                    Self::check_voucher(dest, amount, expiry, *nonce, signature).map_err(
+                        |e| match e {
+                            Error::<T>::VoucherExpired => InvalidTransaction::Stale,
+                            Error::<T>::VoucherAlreadyUsed => InvalidTransaction::Stale,
+                            _ => InvalidTransaction::BadProof,
+                        },
+                    )?;
+                    Self::window_total(&AssetKind::Native, dest, *amount).map_err(|e| match e {
+                        Error::<T>::AmountTooHigh | Error::<T>::AssetNotEnabled => {
+                            InvalidTransaction::Call
+                        }
+                        _ => InvalidTransaction::ExhaustsResources,
+                    })?;
+                    Self::lifetime_total(&AssetKind::Native, dest, *amount)
+                        .map_err(|_| InvalidTransaction::Call)?;
+
+                    let now = frame_system::Pallet::<T>::block_number();
+                    let longevity = expiry.saturating_sub(now).saturating_add(One::one());
+
+                    ValidTransaction::with_tag_prefix("FaucetVoucher")
+                        .and_provides(nonce)
+                        .longevity(longevity.saturated_into::<u64>())
+                        .propagate(true)
+                        .build()
+                }
+                Call::redeem { code, dest: _ } => {
+                    let (hash, claim_code) = Self::check_claim_code(code).map_err(|e| match e {
+                        Error::<T>::ClaimCodeUsed | Error::<T>::ClaimCodeExpired => {
+                            InvalidTransaction::Stale
+                        }
+                        _ => InvalidTransaction::BadProof,
+                    })?;
+
+                    let now = frame_system::Pallet::<T>::block_number();
+                    let longevity = claim_code
+                        .expiry
+                        .saturating_sub(now)
+                        .saturating_add(One::one());
+
+                    ValidTransaction::with_tag_prefix("FaucetClaimCode")
+                        .and_provides(hash)
+                        .longevity(longevity.saturated_into::<u64>())
+                        .propagate(true)
+                        .build()
+                }
                 _ => InvalidTransaction::Call.into(),
             }
         }
@@ -0,0 +1,83 @@
+//! Storage migrations of the faucet pallet.
+//!
+//! Each migration implements [`UncheckedOnRuntimeUpgrade`] and is exported wrapped in a
+//! [`VersionedMigration`], which only runs it while the on-chain storage version of the pallet
+//! is `FROM`, sets it to `TO` afterwards, and runs its `pre_upgrade`/`post_upgrade` checks under
+//! `try-runtime`. To add one, bump `STORAGE_VERSION` in the pallet, add a `vN` module here and
+//! append its `MigrateToVN` to the runtime's `Migrations`.
+//!
+//! A migration runs within the single upgrade block, so it must only touch a bounded amount of
+//! storage. Work proportional to a map belongs in a hook resuming from a cursor, like the
+//! `LastRequests` cleanup of `on_idle`.
+
+use crate::{BalanceOf, Config, LastRequests, Pallet};
+#[cfg(feature = "try-runtime")]
+use codec::alloc::vec::Vec;
+use codec::Decode;
+#[cfg(feature = "try-runtime")]
+use polkadot_sdk::{frame_support::ensure, sp_runtime::TryRuntimeError};
+use polkadot_sdk::{
+    frame_support::{
+        migrations::VersionedMigration,
+        traits::{Get, UncheckedOnRuntimeUpgrade},
+        weights::Weight,
+    },
+    frame_system::{self, pallet_prelude::BlockNumberFor},
+};
+
+pub mod v1 {
+    use super::*;
+
+    /// Migrates the faucet from the unversioned storage to v1, see [`UncheckedMigrateToV1`].
+    pub type MigrateToV1<T, OldBalance> = VersionedMigration<
+        0,
+        1,
+        UncheckedMigrateToV1<T, OldBalance>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+
+    /// Translates the `LastRequests` totals from `OldBalance` to the current balance.
+    ///
+    /// `LastRequests` is the only item stored before v1 and its layout is otherwise unchanged,
+    /// so `OldBalance` being the current balance only sets the storage version. Every entry is
+    /// read and written once within the upgrade block, as before v1 no cleanup bounded the map.
+    pub struct UncheckedMigrateToV1<T, OldBalance>(core::marker::PhantomData<(T, OldBalance)>);
+
+    impl<T: Config, OldBalance: Decode + Into<BalanceOf<T>>> UncheckedOnRuntimeUpgrade
+        for UncheckedMigrateToV1<T, OldBalance>
+    {
+        fn on_runtime_upgrade() -> Weight {
+            let mut translated: u64 = 0;
+            LastRequests::<T>::translate::<(OldBalance, BlockNumberFor<T>), _>(
+                |_, (total, last_time)| {
+                    translated = translated.saturating_add(1);
+                    Some((total.into(), last_time))
+                },
+            );
+
+            T::DbWeight::get().reads_writes(translated, translated)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+            use codec::Encode;
+
+            Ok((LastRequests::<T>::iter_keys().count() as u32).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+            let entries = u32::decode(&mut &state[..])
+                .map_err(|_| "Failed to decode the pre-upgrade state")?;
+
+            // Entries failing to decode are skipped by `iter`
+            ensure!(
+                LastRequests::<T>::iter().count() as u32 == entries,
+                "LastRequests was not translated"
+            );
+
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,143 @@
+//! Mock runtime of the faucet pallet.
+
+use crate as pallet_minimal_template;
+use polkadot_sdk::{
+    frame_support::{
+        parameter_types,
+        traits::{AsEnsureOriginWithArg, ConstU32, ConstU64, WithdrawReasons},
+        weights::constants::RocksDbWeight,
+        PalletId,
+    },
+    frame_system::{EnsureRoot, EnsureSigned},
+    polkadot_sdk_frame::testing_prelude::*,
+    sp_io::TestExternalities,
+    sp_runtime::{testing::TestSignature, traits::ConvertInto, BuildStorage},
+    *,
+};
+
+pub type AccountId = u64;
+pub type Balance = u64;
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+
+/// Balance of the accounts endowed at genesis.
+pub const ENDOWMENT: Balance = 1_000;
+/// Balance of the faucet account at genesis.
+pub const FAUCET_BALANCE: Balance = 10_000;
+/// `FaucetAmount` of the mock runtime.
+pub const FAUCET_AMOUNT: Balance = 100;
+/// `AccumulationPeriod` of the mock runtime.
+pub const ACCUMULATION_PERIOD: u64 = 10;
+/// `MinVestedTransfer` of the mock runtime.
+pub const MIN_VESTED_TRANSFER: Balance = 10;
+/// `QueuedDripLifetime` of the mock runtime.
+pub const QUEUED_DRIP_LIFETIME: u64 = 5;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+construct_runtime!(
+    pub enum Test {
+        System: frame_system,
+        Balances: pallet_balances,
+        Assets: pallet_assets,
+        Vesting: pallet_vesting,
+        Faucet: pallet_minimal_template,
+    }
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+    type Block = Block;
+    type DbWeight = RocksDbWeight;
+    type AccountData = pallet_balances::AccountData<Balance>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+    type AccountStore = System;
+}
+
+#[derive_impl(pallet_assets::config_preludes::TestDefaultConfig)]
+impl pallet_assets::Config for Test {
+    type Balance = Balance;
+    type AssetId = u32;
+    type AssetIdParameter = u32;
+    type Currency = Balances;
+    type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<AccountId>>;
+    type ForceOrigin = EnsureRoot<AccountId>;
+    type Freezer = ();
+}
+
+parameter_types! {
+    pub UnvestedFundsAllowedWithdrawReasons: WithdrawReasons =
+        WithdrawReasons::except(WithdrawReasons::TRANSFER | WithdrawReasons::RESERVE);
+}
+
+impl pallet_vesting::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Currency = Balances;
+    type BlockNumberToBalance = ConvertInto;
+    type MinVestedTransfer = ConstU64<MIN_VESTED_TRANSFER>;
+    type WeightInfo = ();
+    type UnvestedFundsAllowedWithdrawReasons = UnvestedFundsAllowedWithdrawReasons;
+    type BlockNumberProvider = System;
+    const MAX_VESTING_SCHEDULES: u32 = 3;
+}
+
+parameter_types! {
+    pub const FaucetPalletId: PalletId = PalletId(*b"ATFAUCET");
+    pub const MaxMintPerCall: Option<Balance> = Some(1_000);
+    pub const MaxTotalMint: Option<Balance> = Some(10_000);
+    pub static TopUpReserve: Option<AccountId> = Some(BOB);
+}
+
+impl pallet_minimal_template::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Currency = Balances;
+    type AccumulationPeriod = ConstU64<ACCUMULATION_PERIOD>;
+    type FaucetAmount = ConstU64<FAUCET_AMOUNT>;
+    type PalletId = FaucetPalletId;
+    type AdminOrigin = EnsureRoot<AccountId>;
+    type MaxMintPerCall = MaxMintPerCall;
+    type MaxTotalMint = MaxTotalMint;
+    type PowMaxAge = ConstU64<10>;
+    type OperatorSignature = TestSignature;
+    type MaxOperators = ConstU32<4>;
+    type AssetId = u32;
+    type Assets = Assets;
+    type MaxQueueLen = ConstU32<4>;
+    type MaxPayoutsPerBlock = ConstU32<2>;
+    type QueuedDripLifetime = ConstU64<QUEUED_DRIP_LIFETIME>;
+    type MaxListUpdate = ConstU32<16>;
+    type Vesting = Vesting;
+    type MinVestedTransfer = ConstU64<MIN_VESTED_TRANSFER>;
+    type MaxRequestsCheckedPerBlock = ConstU32<2>;
+    type MaxClaimCodesPerCall = ConstU32<16>;
+    type TopUpReserve = TopUpReserve;
+}
+
+/// Externalities with `ALICE` and `BOB` endowed and the faucet account funded,
+/// starting at block 1.
+pub fn new_test_ext() -> TestExternalities {
+    let mut storage = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap();
+
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![(ALICE, ENDOWMENT), (BOB, ENDOWMENT)],
+    }
+    .assimilate_storage(&mut storage)
+    .unwrap();
+
+    pallet_minimal_template::GenesisConfig::<Test> {
+        balance: FAUCET_BALANCE,
+        ..Default::default()
+    }
+    .assimilate_storage(&mut storage)
+    .unwrap();
+
+    let mut ext = TestExternalities::new(storage);
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
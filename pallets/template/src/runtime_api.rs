@@ -0,0 +1,32 @@
+//! Runtime API of the faucet pallet.
+
+use crate::AssetKind;
+use codec::Codec;
+
+polkadot_sdk::sp_api::decl_runtime_apis! {
+    /// API to query the state of the faucet.
+    pub trait FaucetApi<AccountId, AssetId, Balance, BlockNumber>
+    where
+        AccountId: Codec,
+        AssetId: Codec,
+        Balance: Codec,
+        BlockNumber: Codec,
+    {
+        /// Budget of `asset` remaining in the current period, `None` if the budget is unlimited.
+        fn remaining_budget(asset: AssetKind<AssetId>) -> Option<Balance>;
+
+        /// Amount of `asset` `who` can still request, `None` if the asset is not enabled.
+        fn remaining_allowance(asset: AssetKind<AssetId>, who: AccountId) -> Option<Balance>;
+
+        /// Block the window of `who` for `asset` resets at, the current block if it is
+        /// already reset. `None` if the asset is not enabled.
+        fn window_reset(asset: AssetKind<AssetId>, who: AccountId) -> Option<BlockNumber>;
+
+        /// Balance of `asset` the faucet can drip.
+        fn faucet_balance(asset: AssetKind<AssetId>) -> Balance;
+
+        /// Position of the first queued drip of `who`, `0` being paid next.
+        /// `None` if `who` has no queued drip.
+        fn queue_position(who: AccountId) -> Option<u32>;
+    }
+}
@@ -0,0 +1,1389 @@
+//! Tests of the faucet pallet.
+
+use crate::{
+    migrations::v1::MigrateToV1, mock::*, AccessList, AccessMode, AssetFaucetConfig, AssetKind,
+    AssetLastRequests, BudgetConfig, BudgetUsage, Error, Event, ExhaustedBehaviour, LastRequests,
+    LastRequestsCursor, LifetimeReceived, Mode, ProofOfWork, QueuedDrips, TopUp, TopUpConfig,
+    TopUpSource, TotalMinted, UsedVoucherNonces, VOUCHER_CONTEXT,
+};
+use codec::Encode;
+use polkadot_sdk::{
+    frame_support::{
+        assert_noop, assert_ok,
+        dispatch::GetDispatchInfo,
+        storage::unhashed,
+        traits::{
+            fungible::{Inspect, Mutate},
+            fungibles::{Inspect as _, Mutate as _},
+            Get, GetStorageVersion, Hooks, OnRuntimeUpgrade, StorageVersion,
+            UnfilteredDispatchable, VestingSchedule,
+        },
+        weights::{constants::RocksDbWeight, Weight},
+    },
+    frame_system, pallet_vesting,
+    sp_core::H256,
+    sp_io::{hashing::blake2_256, TestExternalities},
+    sp_runtime::{
+        testing::TestSignature,
+        traits::{BadOrigin, ValidateUnsigned},
+        transaction_validity::{InvalidTransaction, TransactionSource, TransactionValidity},
+        DispatchResult,
+    },
+};
+
+fn token_faucet(dest: AccountId, amount: Balance) -> crate::Call<Test> {
+    crate::Call::token_faucet {
+        dest,
+        asset: AssetKind::Native,
+        amount,
+        pow: None,
+    }
+}
+
+fn request(dest: AccountId, amount: Balance) -> DispatchResult {
+    Faucet::token_faucet(RuntimeOrigin::none(), dest, AssetKind::Native, amount, None)
+}
+
+/// Window of the asset created by `create_asset`.
+const ASSET_ACCUMULATION_PERIOD: u64 = 5;
+
+/// Creates the asset `1` with a balance of `FAUCET_BALANCE` in the faucet account, and
+/// enables it with a maximum drip of `FAUCET_AMOUNT`.
+fn create_asset() {
+    assert_ok!(Assets::force_create(
+        RuntimeOrigin::root(),
+        1,
+        ALICE,
+        true,
+        1
+    ));
+    assert_ok!(Assets::mint_into(1, &Faucet::account_id(), FAUCET_BALANCE));
+    assert_ok!(Faucet::set_asset_config(
+        RuntimeOrigin::root(),
+        1,
+        Some(AssetFaucetConfig {
+            max_drip: FAUCET_AMOUNT,
+            accumulation_period: ASSET_ACCUMULATION_PERIOD,
+            enabled: true,
+        })
+    ));
+}
+
+fn request_asset(dest: AccountId, amount: Balance) -> DispatchResult {
+    Faucet::token_faucet(
+        RuntimeOrigin::none(),
+        dest,
+        AssetKind::Asset(1),
+        amount,
+        None,
+    )
+}
+
+#[test]
+fn token_faucet_works() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(request(ALICE, 60));
+
+        assert_eq!(Balances::balance(&ALICE), ENDOWMENT + 60);
+        assert_eq!(
+            Balances::balance(&Faucet::account_id()),
+            FAUCET_BALANCE - 60
+        );
+        assert_eq!(LastRequests::<Test>::get(ALICE), (60, 1));
+        System::assert_last_event(
+            Event::<Test>::Dripped {
+                asset: AssetKind::Native,
+                dest: ALICE,
+                amount: 60,
+                window_total: 60,
+            }
+            .into(),
+        );
+        assert_ok!(Faucet::do_try_state());
+    });
+}
+
+#[test]
+fn try_state_detects_corrupt_last_requests() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(request(ALICE, FAUCET_AMOUNT));
+        assert_ok!(Faucet::do_try_state());
+
+        LastRequests::<Test>::insert(ALICE, (FAUCET_AMOUNT + 1, 1));
+
+        assert!(Faucet::do_try_state().is_err());
+    });
+}
+
+#[test]
+fn token_faucet_requires_none_origin() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Faucet::token_faucet(
+                RuntimeOrigin::signed(ALICE),
+                ALICE,
+                AssetKind::Native,
+                60,
+                None
+            ),
+            BadOrigin
+        );
+    });
+}
+
+#[test]
+fn exact_limit_request_works() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(request(ALICE, FAUCET_AMOUNT));
+
+        assert_eq!(LastRequests::<Test>::get(ALICE), (FAUCET_AMOUNT, 1));
+        assert_noop!(request(ALICE, 1), Error::<Test>::RequestLimitExceeded);
+    });
+}
+
+#[test]
+fn amount_too_high_fails() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            request(ALICE, FAUCET_AMOUNT + 1),
+            Error::<Test>::AmountTooHigh
+        );
+        assert_noop!(request(ALICE, Balance::MAX), Error::<Test>::AmountTooHigh);
+    });
+}
+
+#[test]
+fn requests_accumulate_within_window() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(request(ALICE, 60));
+
+        System::set_block_number(5);
+        assert_ok!(request(ALICE, 40));
+        // The window start is kept, it doesn't move with every request
+        assert_eq!(LastRequests::<Test>::get(ALICE), (FAUCET_AMOUNT, 1));
+
+        System::set_block_number(ACCUMULATION_PERIOD);
+        assert_noop!(request(ALICE, 1), Error::<Test>::RequestLimitExceeded);
+    });
+}
+
+#[test]
+fn window_rolls_over_after_accumulation_period() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(request(ALICE, FAUCET_AMOUNT));
+
+        System::set_block_number(1 + ACCUMULATION_PERIOD);
+        assert_ok!(request(ALICE, FAUCET_AMOUNT));
+
+        assert_eq!(
+            LastRequests::<Test>::get(ALICE),
+            (FAUCET_AMOUNT, 1 + ACCUMULATION_PERIOD)
+        );
+        assert_eq!(Balances::balance(&ALICE), ENDOWMENT + 2 * FAUCET_AMOUNT);
+    });
+}
+
+#[test]
+fn window_total_does_not_overflow() {
+    new_test_ext().execute_with(|| {
+        LastRequests::<Test>::insert(ALICE, (Balance::MAX, 1));
+
+        assert_noop!(request(ALICE, 1), Error::<Test>::RequestLimitExceeded);
+    });
+}
+
+fn faucet_dry_events() -> usize {
+    System::events()
+        .iter()
+        .filter(|record| matches!(record.event, RuntimeEvent::Faucet(Event::FaucetDry { .. })))
+        .count()
+}
+
+#[test]
+fn faucet_dry_is_emitted_once() {
+    new_test_ext().execute_with(|| {
+        Balances::set_balance(&Faucet::account_id(), FAUCET_AMOUNT + 50);
+
+        assert_ok!(request(ALICE, 40));
+        assert_eq!(faucet_dry_events(), 0);
+
+        assert_ok!(request(BOB, 20));
+        System::assert_has_event(
+            Event::<Test>::FaucetDry {
+                asset: AssetKind::Native,
+                balance: FAUCET_AMOUNT - 10,
+            }
+            .into(),
+        );
+
+        // The balance is still below the maximum drip
+        assert_ok!(request(3, 20));
+        assert_eq!(faucet_dry_events(), 1);
+    });
+}
+
+#[test]
+fn not_enough_faucet_balance_fails() {
+    new_test_ext().execute_with(|| {
+        Balances::set_balance(&Faucet::account_id(), 50);
+
+        assert_noop!(request(ALICE, 60), Error::<Test>::NotEnoughFaucetBalance);
+        // The faucet account may be drained completely
+        assert_ok!(request(ALICE, 50));
+        assert_eq!(Balances::balance(&Faucet::account_id()), 0);
+    });
+}
+
+#[test]
+fn refill_pallet_keeps_sender_alive() {
+    new_test_ext().execute_with(|| {
+        assert!(Faucet::refill_pallet(RuntimeOrigin::signed(ALICE), ENDOWMENT).is_err());
+        assert_eq!(Balances::balance(&ALICE), ENDOWMENT);
+
+        assert_ok!(Faucet::refill_pallet(
+            RuntimeOrigin::signed(ALICE),
+            ENDOWMENT - 1
+        ));
+
+        assert_eq!(Balances::balance(&ALICE), 1);
+        assert_eq!(
+            Balances::balance(&Faucet::account_id()),
+            FAUCET_BALANCE + ENDOWMENT - 1
+        );
+        System::assert_last_event(
+            Event::<Test>::Refilled {
+                from: ALICE,
+                amount: ENDOWMENT - 1,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn validate_unsigned_accepts_valid_request() {
+    new_test_ext().execute_with(|| {
+        let validity = Faucet::validate_unsigned(
+            TransactionSource::External,
+            &token_faucet(ALICE, FAUCET_AMOUNT),
+        );
+
+        let validity = validity.expect("Request within the limits is valid");
+        assert!(validity.propagate);
+        assert_eq!(validity.provides.len(), 1);
+    });
+}
+
+#[test]
+fn validate_unsigned_rejects_invalid_requests() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(
+            Faucet::validate_unsigned(
+                TransactionSource::External,
+                &token_faucet(ALICE, FAUCET_AMOUNT + 1)
+            ),
+            Err(InvalidTransaction::Call.into())
+        );
+
+        assert_ok!(request(ALICE, FAUCET_AMOUNT));
+        assert_eq!(
+            Faucet::validate_unsigned(TransactionSource::External, &token_faucet(ALICE, 1)),
+            Err(InvalidTransaction::ExhaustsResources.into())
+        );
+
+        let set_balance = crate::Call::<Test>::set_balance {
+            who: BOB,
+            amount: 10,
+        };
+        assert_eq!(
+            Faucet::validate_unsigned(TransactionSource::External, &set_balance),
+            Err(InvalidTransaction::Call.into())
+        );
+    });
+}
+
+/// Difficulty of the proof of work tests, a valid nonce has a zero first byte.
+const POW_DIFFICULTY: u8 = 8;
+
+fn validate_pow(pow: Option<ProofOfWork<u64>>) -> TransactionValidity {
+    let call = crate::Call::<Test>::token_faucet {
+        dest: ALICE,
+        asset: AssetKind::Native,
+        amount: FAUCET_AMOUNT,
+        pow,
+    };
+    Faucet::validate_unsigned(TransactionSource::External, &call)
+}
+
+/// Proof of work of `ALICE` requesting `FAUCET_AMOUNT`, bound to `block_number`,
+/// which is valid at `POW_DIFFICULTY` if `valid`.
+fn find_pow(block_number: u64, valid: bool) -> ProofOfWork<u64> {
+    let block_hash = System::block_hash(block_number);
+    let nonce = (0..)
+        .find(|nonce| {
+            let hash = Faucet::pow_hash(
+                &ALICE,
+                &AssetKind::Native,
+                &block_hash,
+                &FAUCET_AMOUNT,
+                *nonce,
+            );
+            (hash[0] == 0) == valid
+        })
+        .unwrap();
+
+    ProofOfWork {
+        block_number,
+        nonce,
+    }
+}
+
+/// Externalities at block 5 with the hashes of blocks 1 to 4 and `POW_DIFFICULTY` set.
+fn new_pow_test_ext() -> TestExternalities {
+    let mut ext = new_test_ext();
+    ext.execute_with(|| {
+        for n in 1..5 {
+            frame_system::BlockHash::<Test>::insert(n, H256::repeat_byte(n as u8));
+        }
+        System::set_block_number(5);
+        assert_ok!(Faucet::set_pow_difficulty(
+            RuntimeOrigin::root(),
+            POW_DIFFICULTY
+        ));
+    });
+    ext
+}
+
+#[test]
+fn pow_is_not_required_at_difficulty_zero() {
+    new_test_ext().execute_with(|| {
+        let validity = validate_pow(None).unwrap();
+
+        assert_eq!(validity.longevity, 10);
+    });
+}
+
+#[test]
+fn valid_pow_is_accepted() {
+    new_pow_test_ext().execute_with(|| {
+        let validity = validate_pow(Some(find_pow(4, true))).unwrap();
+
+        // Valid until the bound block is `PowMaxAge` blocks old
+        assert_eq!(validity.longevity, 9);
+        assert_eq!(validate_pow(Some(find_pow(1, true))).unwrap().longevity, 6);
+    });
+}
+
+#[test]
+fn missing_or_bad_pow_is_rejected() {
+    new_pow_test_ext().execute_with(|| {
+        assert_eq!(validate_pow(None), Err(InvalidTransaction::BadProof.into()));
+        assert_eq!(
+            validate_pow(Some(find_pow(4, false))),
+            Err(InvalidTransaction::BadProof.into())
+        );
+    });
+}
+
+#[test]
+fn pow_is_bound_to_asset() {
+    new_pow_test_ext().execute_with(|| {
+        assert_ok!(Faucet::set_asset_config(
+            RuntimeOrigin::root(),
+            1,
+            Some(AssetFaucetConfig {
+                max_drip: FAUCET_AMOUNT,
+                accumulation_period: ACCUMULATION_PERIOD,
+                enabled: true,
+            })
+        ));
+        let block_hash = System::block_hash(4);
+        let solves = |asset: AssetKind<u32>, nonce: u64| {
+            Faucet::pow_hash(&ALICE, &asset, &block_hash, &FAUCET_AMOUNT, nonce)[0] == 0
+        };
+        let nonce = (0..)
+            .find(|nonce| solves(AssetKind::Native, *nonce) && !solves(AssetKind::Asset(1), *nonce))
+            .unwrap();
+        let pow = ProofOfWork {
+            block_number: 4,
+            nonce,
+        };
+        assert_ok!(validate_pow(Some(pow.clone())));
+
+        let call = crate::Call::<Test>::token_faucet {
+            dest: ALICE,
+            asset: AssetKind::Asset(1),
+            amount: FAUCET_AMOUNT,
+            pow: Some(pow),
+        };
+        assert_eq!(
+            Faucet::validate_unsigned(TransactionSource::External, &call),
+            Err(InvalidTransaction::BadProof.into())
+        );
+    });
+}
+
+#[test]
+fn pow_bound_to_future_or_old_block_is_rejected() {
+    new_pow_test_ext().execute_with(|| {
+        assert_eq!(
+            validate_pow(Some(find_pow(5, true))),
+            Err(InvalidTransaction::Future.into())
+        );
+        assert_eq!(
+            validate_pow(Some(find_pow(6, true))),
+            Err(InvalidTransaction::Future.into())
+        );
+
+        System::set_block_number(15);
+        // Older than `PowMaxAge`
+        assert_eq!(
+            validate_pow(Some(find_pow(4, true))),
+            Err(InvalidTransaction::Stale.into())
+        );
+        // No hash is known for the block
+        assert_eq!(
+            validate_pow(Some(find_pow(5, true))),
+            Err(InvalidTransaction::Stale.into())
+        );
+    });
+}
+
+/// Operator signing the vouchers of the tests.
+const OPERATOR: AccountId = 3;
+
+fn sign_voucher(signer: AccountId, dest: AccountId, expiry: u64, nonce: u64) -> TestSignature {
+    TestSignature(
+        signer,
+        Faucet::voucher_payload(&dest, &FAUCET_AMOUNT, &expiry, nonce),
+    )
+}
+
+fn voucher(
+    dest: AccountId,
+    expiry: u64,
+    nonce: u64,
+    signature: TestSignature,
+) -> crate::Call<Test> {
+    crate::Call::claim_voucher {
+        dest,
+        amount: FAUCET_AMOUNT,
+        expiry,
+        nonce,
+        signature,
+    }
+}
+
+/// Asserts that `call` is rejected by `validate_unsigned` with `invalid` and fails
+/// with `error` when dispatched.
+fn assert_voucher_rejected(
+    call: crate::Call<Test>,
+    invalid: InvalidTransaction,
+    error: Error<Test>,
+) {
+    assert_eq!(
+        Faucet::validate_unsigned(TransactionSource::External, &call),
+        Err(invalid.into())
+    );
+    assert_noop!(call.dispatch_bypass_filter(RuntimeOrigin::none()), error);
+}
+
+fn new_voucher_test_ext() -> TestExternalities {
+    let mut ext = new_test_ext();
+    ext.execute_with(|| assert_ok!(Faucet::add_operator(RuntimeOrigin::root(), OPERATOR)));
+    ext
+}
+
+#[test]
+fn claim_voucher_works() {
+    new_voucher_test_ext().execute_with(|| {
+        let call = voucher(ALICE, 10, 7, sign_voucher(OPERATOR, ALICE, 10, 7));
+
+        let validity = Faucet::validate_unsigned(TransactionSource::External, &call).unwrap();
+        assert_eq!(validity.longevity, 10);
+        assert_ok!(call.dispatch_bypass_filter(RuntimeOrigin::none()));
+
+        assert_eq!(Balances::balance(&ALICE), ENDOWMENT + FAUCET_AMOUNT);
+        assert_eq!(UsedVoucherNonces::<Test>::get(7), Some(10));
+        System::assert_last_event(
+            Event::<Test>::VoucherClaimed {
+                operator: OPERATOR,
+                dest: ALICE,
+                nonce: 7,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn voucher_of_non_operator_is_rejected() {
+    new_voucher_test_ext().execute_with(|| {
+        assert_voucher_rejected(
+            voucher(ALICE, 10, 7, sign_voucher(BOB, ALICE, 10, 7)),
+            InvalidTransaction::BadProof,
+            Error::<Test>::InvalidVoucherSignature,
+        );
+        // Signed for another destination
+        assert_voucher_rejected(
+            voucher(ALICE, 10, 7, sign_voucher(OPERATOR, BOB, 10, 7)),
+            InvalidTransaction::BadProof,
+            Error::<Test>::InvalidVoucherSignature,
+        );
+    });
+}
+
+#[test]
+fn voucher_nonce_is_used_once() {
+    new_voucher_test_ext().execute_with(|| {
+        let call = voucher(ALICE, 10, 7, sign_voucher(OPERATOR, ALICE, 10, 7));
+        assert_ok!(call.dispatch_bypass_filter(RuntimeOrigin::none()));
+
+        assert_voucher_rejected(
+            voucher(BOB, 10, 7, sign_voucher(OPERATOR, BOB, 10, 7)),
+            InvalidTransaction::Stale,
+            Error::<Test>::VoucherAlreadyUsed,
+        );
+    });
+}
+
+#[test]
+fn expired_voucher_is_rejected() {
+    new_voucher_test_ext().execute_with(|| {
+        System::set_block_number(4);
+
+        assert_voucher_rejected(
+            voucher(ALICE, 3, 7, sign_voucher(OPERATOR, ALICE, 3, 7)),
+            InvalidTransaction::Stale,
+            Error::<Test>::VoucherExpired,
+        );
+    });
+}
+
+#[test]
+fn voucher_of_other_chain_is_rejected() {
+    new_voucher_test_ext().execute_with(|| {
+        let other_genesis_hash = H256::repeat_byte(1);
+        assert_ne!(System::block_hash(0), other_genesis_hash);
+        let payload = (
+            VOUCHER_CONTEXT,
+            other_genesis_hash,
+            ALICE,
+            FAUCET_AMOUNT,
+            10u64,
+            7u64,
+        )
+            .encode();
+
+        assert_voucher_rejected(
+            voucher(ALICE, 10, 7, TestSignature(OPERATOR, payload)),
+            InvalidTransaction::BadProof,
+            Error::<Test>::InvalidVoucherSignature,
+        );
+    });
+}
+
+/// Vesting period of the vesting tests.
+const VESTING_PERIOD: u64 = 10;
+
+fn new_vesting_test_ext() -> TestExternalities {
+    let mut ext = new_voucher_test_ext();
+    ext.execute_with(|| {
+        assert_ok!(Faucet::set_native_vesting_period(
+            RuntimeOrigin::root(),
+            Some(VESTING_PERIOD)
+        ))
+    });
+    ext
+}
+
+#[test]
+fn token_faucet_drip_is_vested() {
+    new_vesting_test_ext().execute_with(|| {
+        assert_ok!(request(ALICE, 60));
+
+        assert_eq!(Balances::balance(&ALICE), ENDOWMENT + 60);
+        assert_eq!(Vesting::vesting_balance(&ALICE), Some(60));
+    });
+}
+
+#[test]
+fn vested_amount_below_minimum_is_rejected() {
+    new_vesting_test_ext().execute_with(|| {
+        assert_eq!(
+            Faucet::validate_unsigned(
+                TransactionSource::External,
+                &token_faucet(ALICE, MIN_VESTED_TRANSFER - 1)
+            ),
+            Err(InvalidTransaction::Call.into())
+        );
+        assert_noop!(
+            request(ALICE, MIN_VESTED_TRANSFER - 1),
+            Error::<Test>::VestedAmountTooLow
+        );
+
+        assert_ok!(request(ALICE, MIN_VESTED_TRANSFER));
+    });
+}
+
+#[test]
+fn vesting_schedules_limit_is_checked() {
+    new_vesting_test_ext().execute_with(|| {
+        let max_schedules = <Test as pallet_vesting::Config>::MAX_VESTING_SCHEDULES;
+        for _ in 0..max_schedules {
+            assert_ok!(request(ALICE, MIN_VESTED_TRANSFER));
+        }
+
+        assert_eq!(
+            Faucet::validate_unsigned(
+                TransactionSource::External,
+                &token_faucet(ALICE, MIN_VESTED_TRANSFER)
+            ),
+            Err(InvalidTransaction::ExhaustsResources.into())
+        );
+        assert_noop!(
+            request(ALICE, MIN_VESTED_TRANSFER),
+            Error::<Test>::TooManyVestingSchedules
+        );
+    });
+}
+
+#[test]
+fn voucher_drip_is_not_vested() {
+    new_vesting_test_ext().execute_with(|| {
+        let call = voucher(ALICE, 10, 7, sign_voucher(OPERATOR, ALICE, 10, 7));
+        assert_ok!(call.dispatch_bypass_filter(RuntimeOrigin::none()));
+
+        assert_eq!(Balances::balance(&ALICE), ENDOWMENT + FAUCET_AMOUNT);
+        assert_eq!(Vesting::vesting_balance(&ALICE), None);
+    });
+}
+
+#[test]
+fn update_access_list_weight_grows_with_input() {
+    let update = |accounts: Vec<AccountId>| {
+        crate::Call::<Test>::update_access_list {
+            list: AccessList::Allowlist,
+            add: accounts.clone().try_into().unwrap(),
+            remove: accounts.try_into().unwrap(),
+        }
+        .get_dispatch_info()
+        .weight
+    };
+
+    assert_eq!(
+        update(vec![1, 2, 3]).saturating_sub(update(vec![])),
+        RocksDbWeight::get().writes(6)
+    );
+}
+
+fn add_to_access_list(list: AccessList, who: AccountId) {
+    assert_ok!(Faucet::update_access_list(
+        RuntimeOrigin::root(),
+        list,
+        vec![who].try_into().unwrap(),
+        Default::default()
+    ));
+}
+
+/// Asserts that a `token_faucet` request of `dest` is rejected by `validate_unsigned` and
+/// fails with `error` when dispatched.
+fn assert_access_rejected(dest: AccountId, error: Error<Test>) {
+    assert_eq!(
+        Faucet::validate_unsigned(TransactionSource::External, &token_faucet(dest, 60)),
+        Err(InvalidTransaction::BadSigner.into())
+    );
+    assert_noop!(request(dest, 60), error);
+}
+
+#[test]
+fn denylisted_dest_is_rejected() {
+    new_test_ext().execute_with(|| {
+        add_to_access_list(AccessList::Denylist, ALICE);
+        assert_access_rejected(ALICE, Error::<Test>::Denied);
+
+        // The denylist also applies to allowlisted destinations
+        assert_ok!(Faucet::set_access_mode(
+            RuntimeOrigin::root(),
+            AccessMode::AllowlistOnly
+        ));
+        add_to_access_list(AccessList::Allowlist, ALICE);
+        assert_access_rejected(ALICE, Error::<Test>::Denied);
+
+        assert_ok!(request(BOB, 60));
+    });
+}
+
+#[test]
+fn allowlist_mode_rejects_other_dests() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Faucet::set_access_mode(
+            RuntimeOrigin::root(),
+            AccessMode::AllowlistOnly
+        ));
+        add_to_access_list(AccessList::Allowlist, ALICE);
+
+        assert_access_rejected(BOB, Error::<Test>::NotAllowlisted);
+        assert!(
+            Faucet::validate_unsigned(TransactionSource::External, &token_faucet(ALICE, 60))
+                .is_ok()
+        );
+        assert_ok!(request(ALICE, 60));
+    });
+}
+
+#[test]
+fn set_access_mode_requires_admin_origin() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Faucet::set_access_mode(RuntimeOrigin::signed(ALICE), AccessMode::AllowlistOnly),
+            BadOrigin
+        );
+
+        assert_ok!(Faucet::set_access_mode(
+            RuntimeOrigin::root(),
+            AccessMode::AllowlistOnly
+        ));
+        assert_eq!(Mode::<Test>::get(), AccessMode::AllowlistOnly);
+        System::assert_last_event(
+            Event::<Test>::AccessModeSet {
+                mode: AccessMode::AllowlistOnly,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn on_idle_clears_expired_requests() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(request(ALICE, FAUCET_AMOUNT));
+        System::set_block_number(5);
+        assert_ok!(request(BOB, FAUCET_AMOUNT));
+
+        System::set_block_number(1 + ACCUMULATION_PERIOD);
+        Faucet::on_idle(1 + ACCUMULATION_PERIOD, Weight::MAX);
+
+        assert!(!LastRequests::<Test>::contains_key(ALICE));
+        assert!(LastRequests::<Test>::contains_key(BOB));
+
+        // The cleared entry behaves like an expired one
+        assert_ok!(request(ALICE, FAUCET_AMOUNT));
+    });
+}
+
+#[test]
+fn on_idle_continues_from_cursor() {
+    new_test_ext().execute_with(|| {
+        for who in [ALICE, BOB, 3] {
+            assert_ok!(request(who, FAUCET_AMOUNT));
+        }
+
+        System::set_block_number(1 + ACCUMULATION_PERIOD);
+        // Checks `MaxRequestsCheckedPerBlock` entries per block
+        Faucet::on_idle(1 + ACCUMULATION_PERIOD, Weight::MAX);
+        assert_eq!(LastRequests::<Test>::iter().count(), 1);
+        assert!(LastRequestsCursor::<Test>::get().is_some());
+
+        Faucet::on_idle(1 + ACCUMULATION_PERIOD, Weight::MAX);
+        assert_eq!(LastRequests::<Test>::iter().count(), 0);
+        assert_eq!(LastRequestsCursor::<Test>::get(), None);
+    });
+}
+
+#[test]
+fn on_idle_clears_expired_asset_requests() {
+    new_test_ext().execute_with(|| {
+        create_asset();
+        assert_ok!(request_asset(ALICE, FAUCET_AMOUNT));
+        System::set_block_number(3);
+        assert_ok!(request_asset(BOB, FAUCET_AMOUNT));
+
+        System::set_block_number(1 + ASSET_ACCUMULATION_PERIOD);
+        Faucet::on_idle(1 + ASSET_ACCUMULATION_PERIOD, Weight::MAX);
+
+        assert!(!AssetLastRequests::<Test>::contains_key(1, ALICE));
+        assert!(AssetLastRequests::<Test>::contains_key(1, BOB));
+        assert_ok!(request_asset(ALICE, FAUCET_AMOUNT));
+
+        // The windows of a removed asset are cleared, once the sweep reached the end of the
+        // map and started over
+        assert_ok!(Faucet::set_asset_config(RuntimeOrigin::root(), 1, None));
+        for _ in 0..2 {
+            Faucet::on_idle(1 + ASSET_ACCUMULATION_PERIOD, Weight::MAX);
+        }
+        assert_eq!(AssetLastRequests::<Test>::iter().count(), 0);
+    });
+}
+
+#[test]
+fn on_idle_clears_expired_voucher_nonces() {
+    new_voucher_test_ext().execute_with(|| {
+        let call = voucher(ALICE, 10, 7, sign_voucher(OPERATOR, ALICE, 10, 7));
+        assert_ok!(call.dispatch_bypass_filter(RuntimeOrigin::none()));
+        let call = voucher(BOB, 20, 8, sign_voucher(OPERATOR, BOB, 20, 8));
+        assert_ok!(call.dispatch_bypass_filter(RuntimeOrigin::none()));
+
+        System::set_block_number(11);
+        Faucet::on_idle(11, Weight::MAX);
+
+        assert_eq!(UsedVoucherNonces::<Test>::get(7), None);
+        assert_eq!(UsedVoucherNonces::<Test>::get(8), Some(20));
+    });
+}
+
+#[test]
+fn migrate_to_v1_translates_last_requests() {
+    new_test_ext().execute_with(|| {
+        StorageVersion::new(0).put::<Faucet>();
+        // Unversioned entry with a `u32` total
+        let key = LastRequests::<Test>::hashed_key_for(ALICE);
+        unhashed::put(&key, &(60u32, 1u64));
+
+        MigrateToV1::<Test, u32>::on_runtime_upgrade();
+
+        assert_eq!(Faucet::on_chain_storage_version(), 1);
+        assert_eq!(LastRequests::<Test>::get(ALICE), (60, 1));
+
+        // Does nothing once the pallet is at v1
+        unhashed::put(&key, &(60u32, 1u64));
+        MigrateToV1::<Test, u32>::on_runtime_upgrade();
+        assert_eq!(unhashed::get::<(u32, u64)>(&key), Some((60, 1)));
+    });
+}
+
+#[test]
+fn queue_mode_pays_in_on_initialize() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Faucet::set_queue_mode(RuntimeOrigin::root(), true));
+
+        assert_ok!(request(ALICE, 60));
+        assert_ok!(request(BOB, 40));
+
+        assert_eq!(Balances::balance(&ALICE), ENDOWMENT);
+        assert_eq!(Faucet::queue_position(&ALICE), Some(0));
+        assert_eq!(Faucet::queue_position(&BOB), Some(1));
+        System::assert_last_event(
+            Event::<Test>::DripQueued {
+                asset: AssetKind::Native,
+                dest: BOB,
+                amount: 40,
+            }
+            .into(),
+        );
+
+        Faucet::on_initialize(2);
+
+        assert_eq!(Balances::balance(&ALICE), ENDOWMENT + 60);
+        assert_eq!(Balances::balance(&BOB), ENDOWMENT + 40);
+        assert_eq!(Faucet::queue_position(&ALICE), None);
+        System::assert_has_event(
+            Event::<Test>::QueuedDripPaid {
+                asset: AssetKind::Native,
+                dest: ALICE,
+                amount: 60,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn queued_drip_expires() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Faucet::set_queue_mode(RuntimeOrigin::root(), true));
+        assert_ok!(request(ALICE, 60));
+        Balances::set_balance(&Faucet::account_id(), 0);
+
+        Faucet::on_initialize(2);
+        assert_eq!(Faucet::queue_position(&ALICE), Some(0));
+
+        System::set_block_number(1 + QUEUED_DRIP_LIFETIME);
+        Faucet::on_initialize(1 + QUEUED_DRIP_LIFETIME);
+
+        assert!(QueuedDrips::<Test>::get().is_empty());
+        assert_eq!(Balances::balance(&ALICE), ENDOWMENT);
+        // The unpaid amount can be requested again
+        assert_eq!(LastRequests::<Test>::get(ALICE), (0, 1));
+        System::assert_last_event(
+            Event::<Test>::QueuedDripExpired {
+                asset: AssetKind::Native,
+                dest: ALICE,
+                amount: 60,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn asset_drip_works() {
+    new_test_ext().execute_with(|| {
+        create_asset();
+        assert_ok!(request(ALICE, 60));
+
+        assert_ok!(request_asset(ALICE, 60));
+        assert_eq!(Assets::balance(1, &ALICE), 60);
+        assert_eq!(
+            Assets::balance(1, &Faucet::account_id()),
+            FAUCET_BALANCE - 60
+        );
+        assert_eq!(AssetLastRequests::<Test>::get(1, ALICE), (60, 1));
+        System::assert_last_event(
+            Event::<Test>::Dripped {
+                asset: AssetKind::Asset(1),
+                dest: ALICE,
+                amount: 60,
+                window_total: 60,
+            }
+            .into(),
+        );
+
+        // The asset window is enforced separately from the native one
+        assert_noop!(
+            request_asset(ALICE, 41),
+            Error::<Test>::RequestLimitExceeded
+        );
+        assert_ok!(request(ALICE, 40));
+
+        // and expires after the accumulation period of the asset
+        System::set_block_number(1 + ASSET_ACCUMULATION_PERIOD);
+        assert_ok!(request_asset(ALICE, FAUCET_AMOUNT));
+        assert_noop!(request(ALICE, 1), Error::<Test>::RequestLimitExceeded);
+        assert_eq!(Assets::balance(1, &ALICE), 60 + FAUCET_AMOUNT);
+        assert_eq!(Balances::balance(&ALICE), ENDOWMENT + FAUCET_AMOUNT);
+    });
+}
+
+#[test]
+fn allowance_follows_window() {
+    new_test_ext().execute_with(|| {
+        create_asset();
+        assert_ok!(request(ALICE, 60));
+        assert_ok!(request_asset(ALICE, 30));
+
+        assert_eq!(
+            Faucet::allowance(&AssetKind::Native, &ALICE),
+            Some((40, 1 + ACCUMULATION_PERIOD))
+        );
+        assert_eq!(
+            Faucet::allowance(&AssetKind::Asset(1), &ALICE),
+            Some((70, 1 + ASSET_ACCUMULATION_PERIOD))
+        );
+        // Not enabled
+        assert_eq!(Faucet::allowance(&AssetKind::Asset(2), &ALICE), None);
+
+        // An expired window resets at the current block
+        System::set_block_number(1 + ACCUMULATION_PERIOD);
+        assert_eq!(
+            Faucet::allowance(&AssetKind::Native, &ALICE),
+            Some((FAUCET_AMOUNT, 1 + ACCUMULATION_PERIOD))
+        );
+
+        // and is capped by the lifetime cap
+        set_budget(1_000, Some(150), ExhaustedBehaviour::Reject);
+        assert_eq!(
+            Faucet::allowance(&AssetKind::Native, &ALICE),
+            Some((90, 1 + ACCUMULATION_PERIOD))
+        );
+    });
+}
+
+#[test]
+fn lowering_max_drip_keeps_try_state() {
+    new_test_ext().execute_with(|| {
+        create_asset();
+        assert_ok!(request_asset(ALICE, FAUCET_AMOUNT));
+
+        assert_ok!(Faucet::set_asset_config(
+            RuntimeOrigin::root(),
+            1,
+            Some(AssetFaucetConfig {
+                max_drip: 10,
+                accumulation_period: ASSET_ACCUMULATION_PERIOD,
+                enabled: true,
+            })
+        ));
+
+        assert_ok!(Faucet::do_try_state());
+        assert_noop!(request_asset(ALICE, 1), Error::<Test>::RequestLimitExceeded);
+    });
+}
+
+/// Length of the budget period set by `set_budget`.
+const BUDGET_PERIOD: u64 = 4;
+
+fn set_budget(budget: Balance, lifetime_cap: Option<Balance>, on_exhausted: ExhaustedBehaviour) {
+    assert_ok!(Faucet::set_budget(
+        RuntimeOrigin::root(),
+        AssetKind::Native,
+        Some(BudgetConfig {
+            budget,
+            period: BUDGET_PERIOD,
+            lifetime_cap,
+            on_exhausted,
+        })
+    ));
+}
+
+#[test]
+fn set_budget_requires_admin_origin() {
+    new_test_ext().execute_with(|| {
+        let config = BudgetConfig {
+            budget: 100,
+            period: BUDGET_PERIOD,
+            lifetime_cap: None,
+            on_exhausted: ExhaustedBehaviour::Reject,
+        };
+        assert_noop!(
+            Faucet::set_budget(
+                RuntimeOrigin::signed(ALICE),
+                AssetKind::Native,
+                Some(config.clone())
+            ),
+            BadOrigin
+        );
+
+        assert_ok!(Faucet::set_budget(
+            RuntimeOrigin::root(),
+            AssetKind::Native,
+            Some(config.clone())
+        ));
+        System::assert_last_event(
+            Event::<Test>::BudgetSet {
+                asset: AssetKind::Native,
+                config: Some(config),
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn exhausted_budget_rejects_drip() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(Faucet::remaining_budget(&AssetKind::Native), None);
+        set_budget(100, None, ExhaustedBehaviour::Reject);
+        assert_eq!(Faucet::remaining_budget(&AssetKind::Native), Some(100));
+
+        assert_ok!(request(ALICE, 60));
+        assert_eq!(Faucet::remaining_budget(&AssetKind::Native), Some(40));
+
+        assert_noop!(request(BOB, 41), Error::<Test>::BudgetExhausted);
+        assert_ok!(request(BOB, 40));
+        assert_eq!(Faucet::remaining_budget(&AssetKind::Native), Some(0));
+        assert_eq!(Balances::balance(&BOB), ENDOWMENT + 40);
+    });
+}
+
+#[test]
+fn budget_resets_after_period() {
+    new_test_ext().execute_with(|| {
+        set_budget(100, None, ExhaustedBehaviour::Reject);
+        assert_ok!(request(ALICE, 100));
+        assert_noop!(request(BOB, 1), Error::<Test>::BudgetExhausted);
+
+        System::set_block_number(BUDGET_PERIOD);
+        assert_eq!(Faucet::remaining_budget(&AssetKind::Native), Some(100));
+        assert_ok!(request(BOB, 60));
+        assert_eq!(Faucet::remaining_budget(&AssetKind::Native), Some(40));
+        assert_eq!(
+            BudgetUsage::<Test>::get(AssetKind::Native),
+            (60, BUDGET_PERIOD)
+        );
+    });
+}
+
+#[test]
+fn lifetime_cap_is_enforced() {
+    new_test_ext().execute_with(|| {
+        set_budget(1_000, Some(150), ExhaustedBehaviour::Reject);
+        assert_ok!(request(ALICE, FAUCET_AMOUNT));
+
+        // The cap applies across windows
+        System::set_block_number(1 + ACCUMULATION_PERIOD);
+        assert_noop!(request(ALICE, 51), Error::<Test>::LifetimeCapExceeded);
+        assert_ok!(request(ALICE, 50));
+        assert_eq!(LifetimeReceived::<Test>::get(AssetKind::Native, ALICE), 150);
+        assert_eq!(Balances::balance(&ALICE), ENDOWMENT + 150);
+    });
+}
+
+#[test]
+fn exhausted_budget_queues_drip() {
+    new_test_ext().execute_with(|| {
+        set_budget(100, None, ExhaustedBehaviour::Queue);
+        assert_ok!(request(ALICE, 100));
+
+        assert_ok!(request(BOB, 60));
+        assert_eq!(Balances::balance(&BOB), ENDOWMENT);
+        assert_eq!(Faucet::queue_position(&BOB), Some(0));
+        System::assert_last_event(
+            Event::<Test>::DripQueued {
+                asset: AssetKind::Native,
+                dest: BOB,
+                amount: 60,
+            }
+            .into(),
+        );
+
+        // Waits for the next period of the budget
+        Faucet::on_initialize(2);
+        assert_eq!(Faucet::queue_position(&BOB), Some(0));
+
+        System::set_block_number(BUDGET_PERIOD);
+        Faucet::on_initialize(BUDGET_PERIOD);
+        assert_eq!(Faucet::queue_position(&BOB), None);
+        assert_eq!(Balances::balance(&BOB), ENDOWMENT + 60);
+        assert_eq!(Faucet::remaining_budget(&AssetKind::Native), Some(40));
+    });
+}
+
+#[test]
+fn lowering_budget_clamps_usage() {
+    new_test_ext().execute_with(|| {
+        set_budget(100, None, ExhaustedBehaviour::Reject);
+        assert_ok!(request(ALICE, 60));
+
+        set_budget(50, None, ExhaustedBehaviour::Reject);
+        assert_eq!(BudgetUsage::<Test>::get(AssetKind::Native).0, 50);
+        assert_eq!(Faucet::remaining_budget(&AssetKind::Native), Some(0));
+        assert_ok!(Faucet::do_try_state());
+
+        // Removing the budget drops its usage
+        assert_ok!(Faucet::set_budget(
+            RuntimeOrigin::root(),
+            AssetKind::Native,
+            None
+        ));
+        assert!(!BudgetUsage::<Test>::contains_key(AssetKind::Native));
+        assert_eq!(Faucet::remaining_budget(&AssetKind::Native), None);
+    });
+}
+
+#[test]
+fn failed_queued_drip_is_refunded() {
+    new_test_ext().execute_with(|| {
+        let faucet = Faucet::account_id();
+        create_asset();
+        assert_ok!(Faucet::set_queue_mode(RuntimeOrigin::root(), true));
+        assert_ok!(request_asset(ALICE, 60));
+
+        // Disabled while the drip is queued
+        assert_ok!(Faucet::set_asset_config(RuntimeOrigin::root(), 1, None));
+        Faucet::on_initialize(2);
+
+        assert_eq!(Assets::balance(1, &faucet), FAUCET_BALANCE);
+        assert_eq!(Assets::balance(1, &ALICE), 0);
+        assert_eq!(Faucet::queue_position(&ALICE), None);
+        assert_eq!(AssetLastRequests::<Test>::get(1, ALICE), (0, 1));
+        assert_eq!(LifetimeReceived::<Test>::get(AssetKind::Asset(1), ALICE), 0);
+        System::assert_last_event(
+            Event::<Test>::QueuedDripFailed {
+                asset: AssetKind::Asset(1),
+                dest: ALICE,
+                amount: 60,
+                error: Error::<Test>::AssetNotEnabled.into(),
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn top_up_mints_below_low_water_mark() {
+    new_test_ext().execute_with(|| {
+        let config = TopUpConfig {
+            low_water_mark: 500,
+            amount: 1_000,
+            source: TopUpSource::Mint,
+        };
+        assert_ok!(Faucet::set_top_up(RuntimeOrigin::root(), Some(config)));
+
+        Faucet::on_initialize(2);
+        assert_eq!(Balances::balance(&Faucet::account_id()), FAUCET_BALANCE);
+
+        Balances::set_balance(&Faucet::account_id(), 100);
+        Faucet::on_initialize(3);
+
+        assert_eq!(Balances::balance(&Faucet::account_id()), 1_100);
+        assert_eq!(TotalMinted::<Test>::get(), 1_000);
+        System::assert_last_event(
+            Event::<Test>::ToppedUp {
+                source: TopUpSource::Mint,
+                amount: 1_000,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn top_up_keeps_reserve_alive() {
+    new_test_ext().execute_with(|| {
+        let config = TopUpConfig {
+            low_water_mark: 500,
+            amount: 2 * ENDOWMENT,
+            source: TopUpSource::Reserve,
+        };
+        assert_ok!(Faucet::set_top_up(RuntimeOrigin::root(), Some(config)));
+        Balances::set_balance(&Faucet::account_id(), 100);
+
+        Faucet::on_initialize(2);
+
+        assert_eq!(Balances::balance(&BOB), 1);
+        assert_eq!(
+            Balances::balance(&Faucet::account_id()),
+            100 + ENDOWMENT - 1
+        );
+    });
+}
+
+#[test]
+fn reserve_top_up_requires_reserve_account() {
+    new_test_ext().execute_with(|| {
+        TopUpReserve::set(None);
+        let config = TopUpConfig {
+            low_water_mark: 500,
+            amount: 1_000,
+            source: TopUpSource::Reserve,
+        };
+
+        assert_noop!(
+            Faucet::set_top_up(RuntimeOrigin::root(), Some(config)),
+            Error::<Test>::NoTopUpReserve
+        );
+    });
+}
+
+#[test]
+fn claim_code_is_redeemed_once() {
+    new_test_ext().execute_with(|| {
+        let code = b"hackathon-0001".to_vec();
+        let hash = H256(blake2_256(&code));
+        assert_ok!(Faucet::create_claim_codes(
+            RuntimeOrigin::root(),
+            vec![hash].try_into().unwrap(),
+            500,
+            10
+        ));
+
+        let redeem = crate::Call::<Test>::redeem {
+            code: code.clone().try_into().unwrap(),
+            dest: ALICE,
+        };
+        assert_ok!(Faucet::validate_unsigned(
+            TransactionSource::External,
+            &redeem
+        ));
+
+        assert_ok!(Faucet::redeem(
+            RuntimeOrigin::none(),
+            code.clone().try_into().unwrap(),
+            ALICE
+        ));
+        assert_eq!(Balances::balance(&ALICE), ENDOWMENT + 500);
+        System::assert_last_event(
+            Event::<Test>::ClaimCodeRedeemed {
+                hash,
+                dest: ALICE,
+                amount: 500,
+            }
+            .into(),
+        );
+
+        assert_eq!(
+            Faucet::validate_unsigned(TransactionSource::External, &redeem),
+            Err(InvalidTransaction::Stale.into())
+        );
+        assert_noop!(
+            Faucet::redeem(RuntimeOrigin::none(), code.try_into().unwrap(), BOB),
+            Error::<Test>::ClaimCodeUsed
+        );
+    });
+}
+
+#[test]
+fn invalid_claim_codes_are_not_created() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(5);
+        let hash = H256(blake2_256(b"hackathon-0003"));
+
+        assert_noop!(
+            Faucet::create_claim_codes(
+                RuntimeOrigin::root(),
+                vec![hash].try_into().unwrap(),
+                0,
+                10
+            ),
+            Error::<Test>::ZeroClaimCodeAmount
+        );
+        assert_noop!(
+            Faucet::create_claim_codes(
+                RuntimeOrigin::root(),
+                vec![hash].try_into().unwrap(),
+                500,
+                4
+            ),
+            Error::<Test>::ClaimCodeExpired
+        );
+        assert_ok!(Faucet::create_claim_codes(
+            RuntimeOrigin::root(),
+            vec![hash].try_into().unwrap(),
+            500,
+            5
+        ));
+    });
+}
+
+#[test]
+fn create_claim_codes_weight_grows_with_input() {
+    let create = |count: u8| {
+        let hashes: Vec<H256> = (0..count).map(|i| H256::repeat_byte(i)).collect();
+        crate::Call::<Test>::create_claim_codes {
+            hashes: hashes.try_into().unwrap(),
+            amount: 500,
+            expiry: 10,
+        }
+        .get_dispatch_info()
+        .weight
+    };
+
+    assert_eq!(
+        create(3).saturating_sub(create(0)),
+        RocksDbWeight::get().reads_writes(3, 3)
+    );
+}
+
+#[test]
+fn unknown_or_expired_claim_code_is_rejected() {
+    new_test_ext().execute_with(|| {
+        let code = b"hackathon-0002".to_vec();
+        let redeem = crate::Call::<Test>::redeem {
+            code: code.clone().try_into().unwrap(),
+            dest: ALICE,
+        };
+        assert_eq!(
+            Faucet::validate_unsigned(TransactionSource::External, &redeem),
+            Err(InvalidTransaction::BadProof.into())
+        );
+
+        let hash = H256(blake2_256(&code));
+        assert_ok!(Faucet::create_claim_codes(
+            RuntimeOrigin::root(),
+            vec![hash].try_into().unwrap(),
+            500,
+            10
+        ));
+        System::set_block_number(11);
+
+        assert_eq!(
+            Faucet::validate_unsigned(TransactionSource::External, &redeem),
+            Err(InvalidTransaction::Stale.into())
+        );
+        assert_noop!(
+            Faucet::redeem(RuntimeOrigin::none(), code.try_into().unwrap(), ALICE),
+            Error::<Test>::ClaimCodeExpired
+        );
+    });
+}
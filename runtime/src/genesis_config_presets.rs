@@ -0,0 +1,141 @@
+//! Genesis presets of the runtime, used by the node and `chain-spec-builder` to build
+//! chain specs.
+
+use crate::{
+    interface::{AccountId, Balance},
+    BalancesConfig, HookConfig, SudoConfig, TemplateConfig, UNITS,
+};
+use alloc::{vec, vec::Vec};
+use polkadot_sdk::{
+    sp_genesis_builder::{self, PresetId},
+    sp_keyring::AccountKeyring,
+    *,
+};
+use serde_json::Value;
+
+/// Preset of a public staging testnet, with an allowlist-only faucet.
+pub const STAGING_RUNTIME_PRESET: &str = "staging";
+
+/// Sudo key of the staging testnet.
+///
+/// The staging keys are placeholders nobody holds the secret of, replace them with the sr25519
+/// public keys of the staging operators before launching the chain.
+const STAGING_SUDO: [u8; 32] = [0x01; 32];
+/// Faucet voucher operator of the staging testnet, a placeholder like `STAGING_SUDO`.
+const STAGING_OPERATOR: [u8; 32] = [0x02; 32];
+/// Hooks reporter of the staging testnet, a placeholder like `STAGING_SUDO`.
+const STAGING_REPORTER: [u8; 32] = [0x03; 32];
+
+/// Balance endowed to every account of a preset.
+fn endowment() -> Balance {
+    1_000 * UNITS
+}
+
+/// Genesis with `endowed` accounts and the hooks `reporters` funded and allowed to save
+/// chunks, `root` as sudo key and the faucet configured by `faucet`.
+fn testnet_genesis(
+    endowed: Vec<AccountId>,
+    reporters: Vec<AccountId>,
+    root: AccountId,
+    faucet: TemplateConfig,
+) -> Value {
+    // The offchain worker of a reporter pays the fees of its transactions
+    let mut accounts = endowed
+        .into_iter()
+        .chain(reporters.clone())
+        .collect::<Vec<_>>();
+    accounts.sort();
+    accounts.dedup();
+    let balances = accounts
+        .into_iter()
+        .map(|a| (a, endowment()))
+        .collect::<Vec<_>>();
+
+    serde_json::json!({
+        "balances": BalancesConfig { balances },
+        "sudo": SudoConfig { key: Some(root) },
+        "template": faucet,
+        "hook": HookConfig { reporters },
+    })
+}
+
+/// Single-node development chain, all well-known accounts are funded.
+fn development_config_genesis() -> Value {
+    testnet_genesis(
+        AccountKeyring::iter().map(|a| a.to_account_id()).collect(),
+        vec![AccountKeyring::Alice.to_account_id()],
+        AccountKeyring::Alice.to_account_id(),
+        TemplateConfig {
+            balance: endowment() * 100,
+            operators: vec![AccountKeyring::Alice.to_account_id()],
+            ..Default::default()
+        },
+    )
+}
+
+/// Local testnet, `Alice` and `Bob` run the nodes and report the hooks data.
+fn local_testnet_genesis() -> Value {
+    let reporters = vec![
+        AccountKeyring::Alice.to_account_id(),
+        AccountKeyring::Bob.to_account_id(),
+    ];
+
+    testnet_genesis(
+        AccountKeyring::iter().map(|a| a.to_account_id()).collect(),
+        reporters.clone(),
+        AccountKeyring::Alice.to_account_id(),
+        TemplateConfig {
+            balance: endowment() * 100,
+            operators: reporters,
+            ..Default::default()
+        },
+    )
+}
+
+/// Staging testnet, only the registered accounts can request funds from the faucet.
+///
+/// No well-known development account is part of it, see `STAGING_SUDO`.
+fn staging_genesis() -> Value {
+    let sudo = AccountId::from(STAGING_SUDO);
+    let operator = AccountId::from(STAGING_OPERATOR);
+    let endowed = vec![sudo.clone(), operator.clone()];
+
+    testnet_genesis(
+        endowed.clone(),
+        vec![AccountId::from(STAGING_REPORTER)],
+        sudo,
+        TemplateConfig {
+            balance: endowment() * 10,
+            allowlist_only: true,
+            allowlist: endowed,
+            pow_difficulty: 8,
+            operators: vec![operator],
+            ..Default::default()
+        },
+    )
+}
+
+/// Genesis config patch of the preset `id`.
+pub fn get_preset(id: &PresetId) -> Option<Vec<u8>> {
+    let patch = match id.as_ref() {
+        sp_genesis_builder::DEV_RUNTIME_PRESET => development_config_genesis(),
+        sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET => local_testnet_genesis(),
+        STAGING_RUNTIME_PRESET => staging_genesis(),
+        _ => return None,
+    };
+
+    Some(
+        serde_json::to_string(&patch)
+            .expect("serialization to json is expected to work. qed.")
+            .into_bytes(),
+    )
+}
+
+/// Names of the available presets.
+pub fn preset_names() -> Vec<PresetId> {
+    vec![
+        PresetId::from(sp_genesis_builder::DEV_RUNTIME_PRESET),
+        PresetId::from(sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET),
+        PresetId::from(STAGING_RUNTIME_PRESET),
+    ]
+}
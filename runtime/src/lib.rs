@@ -25,11 +25,26 @@ include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
 extern crate alloc;
 
+pub mod genesis_config_presets;
+
 use alloc::{vec, vec::Vec};
-use pallet_transaction_payment::{FeeDetails, RuntimeDispatchInfo};
+use pallet_transaction_payment::{
+    FeeDetails, Multiplier, RuntimeDispatchInfo, TargetedFeeAdjustment,
+};
+use polkadot_sdk::frame_support::traits::{
+    fungible::{Balanced, Credit},
+    Imbalance, OnUnbalanced,
+};
+use polkadot_sdk::frame_support::weights::{
+    constants::{ExtrinsicBaseWeight, RocksDbWeight},
+    ConstantMultiplier, WeightToFeeCoefficient, WeightToFeeCoefficients, WeightToFeePolynomial,
+};
 use polkadot_sdk::frame_support::PalletId;
-use polkadot_sdk::sp_runtime::traits::{IdentifyAccount, Verify};
-use polkadot_sdk::sp_runtime::{generic, MultiAddress, MultiSignature, SaturatedConversion};
+use polkadot_sdk::sp_runtime::traits::{AccountIdConversion, IdentifyAccount, Verify};
+use polkadot_sdk::sp_runtime::{
+    generic, impl_opaque_keys, traits::Bounded, FixedPointNumber, MultiAddress, MultiSignature,
+    Perbill, Percent, Perquintill, SaturatedConversion,
+};
 use polkadot_sdk::{
     polkadot_sdk_frame::{
         self as frame,
@@ -45,10 +60,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     spec_name: create_runtime_str!("minimal-template-runtime"),
     impl_name: create_runtime_str!("minimal-template-runtime"),
     authoring_version: 1,
-    spec_version: 0,
+    spec_version: 1,
     impl_version: 1,
     apis: RUNTIME_API_VERSIONS,
-    transaction_version: 1,
+    transaction_version: 2,
     state_version: 1,
 };
 
@@ -127,12 +142,29 @@ mod runtime {
     /// Pallet for working with hooks
     #[runtime::pallet_index(6)]
     pub type Hook = hooks_pallet::Pallet<Runtime>;
+
+    /// Provides fungible assets besides the native token, e.g. for the faucet.
+    #[runtime::pallet_index(7)]
+    pub type Assets = pallet_assets::Pallet<Runtime>;
+
+    /// Provides vesting schedules, e.g. for the vested faucet drips.
+    #[runtime::pallet_index(8)]
+    pub type Vesting = pallet_vesting::Pallet<Runtime>;
 }
 
 parameter_types! {
     pub const Version: RuntimeVersion = VERSION;
 }
 
+impl_opaque_keys! {
+    /// Keys managed by the node keystore, generated by `author_rotateKeys`.
+    pub struct SessionKeys {
+        pub hook: hooks_pallet::crypto::sr25519::Public,
+        pub hook_ed25519: hooks_pallet::crypto::ed25519::Public,
+        pub hook_ecdsa: hooks_pallet::crypto::ecdsa::Public,
+    }
+}
+
 type AccountId = <<Signature as Verify>::Signer as IdentifyAccount>::AccountId;
 
 pub type Nonce = u32;
@@ -150,7 +182,23 @@ type UncheckedExtrinsic = polkadot_sdk::sp_runtime::generic::UncheckedExtrinsic<
     SignedExtra,
 >;
 
-type Balance = u64;
+/// Balance of the native token and the assets.
+///
+/// Changing the balance type changes the encoding of every stored balance, e.g. `System::Account`
+/// and `Balances::TotalIssuance`, so chains started with the former `u64` balance can't be upgraded
+/// to it and must be restarted from a fresh genesis.
+type Balance = u128;
+
+/// Number of decimals of the native token.
+pub const DECIMALS: u8 = 12;
+/// One native token.
+pub const UNITS: Balance = 10u128.pow(DECIMALS as u32);
+pub const CENTS: Balance = UNITS / 100;
+pub const MILLICENTS: Balance = CENTS / 1_000;
+/// Minimum balance an account needs to exist.
+pub const EXISTENTIAL_DEPOSIT: Balance = MILLICENTS;
+
+type AssetId = u32;
 
 type BlockNumber = u32;
 
@@ -223,6 +271,8 @@ impl hooks_pallet::Config for Runtime {
     type MaxChunks = frame_support::traits::ConstU64<10>;
     type CooldownPeriod = CooldownPeriod;
     type OffChainAuthId = hooks_pallet::crypto::OffchainAuthId;
+    type MaxReporters = frame_support::traits::ConstU32<16>;
+    type AdminOrigin = frame_system::EnsureRoot<AccountId>;
 }
 
 /// Implements the types required for the system pallet.
@@ -230,6 +280,7 @@ impl hooks_pallet::Config for Runtime {
 impl frame_system::Config for Runtime {
     type Block = Block;
     type Version = Version;
+    type DbWeight = RocksDbWeight;
     // Use the account data from the balances pallet
     type AccountData = pallet_balances::AccountData<<Runtime as pallet_balances::Config>::Balance>;
 
@@ -239,9 +290,65 @@ impl frame_system::Config for Runtime {
 // Implements the types required for the balances pallet.
 #[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
 impl pallet_balances::Config for Runtime {
+    type Balance = Balance;
+    type ExistentialDeposit = frame_support::traits::ConstU128<EXISTENTIAL_DEPOSIT>;
     type AccountStore = System;
 }
 
+parameter_types! {
+    pub const AssetDeposit: Balance = 100 * UNITS;
+    pub const AssetAccountDeposit: Balance = UNITS;
+    pub const ApprovalDeposit: Balance = 10 * CENTS;
+    pub const MetadataDepositBase: Balance = 10 * UNITS;
+    pub const MetadataDepositPerByte: Balance = UNITS;
+    pub const AssetsStringLimit: u32 = 50;
+}
+
+// Implements the types required for the assets pallet.
+// Creating an asset is permissionless, the deposits keep it from being spammed.
+impl pallet_assets::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = Balance;
+    type RemoveItemsLimit = frame_support::traits::ConstU32<1000>;
+    type AssetId = AssetId;
+    type AssetIdParameter = AssetId;
+    type Currency = Balances;
+    type CreateOrigin =
+        frame_support::traits::AsEnsureOriginWithArg<frame_system::EnsureSigned<AccountId>>;
+    type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+    type AssetDeposit = AssetDeposit;
+    type AssetAccountDeposit = AssetAccountDeposit;
+    type MetadataDepositBase = MetadataDepositBase;
+    type MetadataDepositPerByte = MetadataDepositPerByte;
+    type ApprovalDeposit = ApprovalDeposit;
+    type StringLimit = AssetsStringLimit;
+    type Freezer = ();
+    type Extra = ();
+    type CallbackHandle = ();
+    type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+    pub const MinVestedTransfer: Balance = 100 * UNITS;
+    pub UnvestedFundsAllowedWithdrawReasons: frame_support::traits::WithdrawReasons =
+        frame_support::traits::WithdrawReasons::except(
+            frame_support::traits::WithdrawReasons::TRANSFER
+                | frame_support::traits::WithdrawReasons::RESERVE,
+        );
+}
+
+// Implements the types required for the vesting pallet.
+impl pallet_vesting::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type Currency = Balances;
+    type BlockNumberToBalance = polkadot_sdk::sp_runtime::traits::ConvertInto;
+    type MinVestedTransfer = MinVestedTransfer;
+    type WeightInfo = ();
+    type UnvestedFundsAllowedWithdrawReasons = UnvestedFundsAllowedWithdrawReasons;
+    type BlockNumberProvider = System;
+    const MAX_VESTING_SCHEDULES: u32 = 28;
+}
+
 // Implements the types required for the sudo pallet.
 #[derive_impl(pallet_sudo::config_preludes::TestDefaultConfig)]
 impl pallet_sudo::Config for Runtime {}
@@ -250,36 +357,144 @@ impl pallet_sudo::Config for Runtime {}
 #[derive_impl(pallet_timestamp::config_preludes::TestDefaultConfig)]
 impl pallet_timestamp::Config for Runtime {}
 
+parameter_types! {
+    /// Fee of an extrinsic of `ExtrinsicBaseWeight`.
+    pub const ExtrinsicBaseFee: Balance = CENTS / 10;
+    /// Fee per byte of an extrinsic.
+    pub const TransactionByteFee: Balance = MILLICENTS;
+    /// Share of the fees and tips paid into the faucet account, the rest is burned.
+    ///
+    /// Zero, burning all fees, unless root sets it with `System::set_storage` under
+    /// `FeesToFaucet::key()` to recycle fees into the faucet.
+    pub storage FeesToFaucet: Percent = Percent::zero();
+    /// Priority boost of operational extrinsics, see
+    /// [`pallet_transaction_payment::Config::OperationalFeeMultiplier`].
+    pub const OperationalFeeMultiplier: u8 = 5;
+    /// Block fullness the fee multiplier targets.
+    pub const TargetBlockFullness: Perquintill = Perquintill::from_percent(25);
+    /// How fast the fee multiplier adjusts, slow enough to not react to a single busy block.
+    pub AdjustmentVariable: Multiplier = Multiplier::saturating_from_rational(1, 100_000);
+    pub MinimumMultiplier: Multiplier = Multiplier::saturating_from_rational(1, 1_000_000_000u128);
+    pub MaximumMultiplier: Multiplier = Bounded::max_value();
+}
+
+/// Linear weight to fee polynomial, mapping `ExtrinsicBaseWeight` to `ExtrinsicBaseFee`.
+pub struct WeightToFee;
+
+impl WeightToFeePolynomial for WeightToFee {
+    type Balance = Balance;
+
+    fn polynomial() -> WeightToFeeCoefficients<Self::Balance> {
+        let p = ExtrinsicBaseFee::get();
+        let q = Balance::from(ExtrinsicBaseWeight::get().ref_time());
+        vec![WeightToFeeCoefficient {
+            degree: 1,
+            negative: false,
+            coeff_frac: Perbill::from_rational(p % q, q),
+            coeff_integer: p / q,
+        }]
+        .into()
+    }
+}
+
+/// Pays `FeesToFaucet` of the fees and tips into the faucet account, burns the rest.
+pub struct DealWithFees;
+
+impl OnUnbalanced<Credit<AccountId, Balances>> for DealWithFees {
+    fn on_unbalanceds(mut fees_then_tips: impl Iterator<Item = Credit<AccountId, Balances>>) {
+        let Some(mut fees) = fees_then_tips.next() else {
+            return;
+        };
+        if let Some(tips) = fees_then_tips.next() {
+            fees = fees.merge(tips);
+        }
+
+        let to_faucet = FeesToFaucet::get().deconstruct() as u32;
+        let (to_faucet, to_burn) = fees.ration(to_faucet, 100 - to_faucet);
+
+        // Dropped credit is burned
+        drop(to_burn);
+        let _ = <Balances as Balanced<AccountId>>::resolve(&Template::account_id(), to_faucet);
+    }
+}
+
 // Implements the types required for the transaction payment pallet.
 #[derive_impl(pallet_transaction_payment::config_preludes::TestDefaultConfig)]
 impl pallet_transaction_payment::Config for Runtime {
-    type OnChargeTransaction = pallet_transaction_payment::FungibleAdapter<Balances, ()>;
-    // Setting fee as independent of the weight of the extrinsic for demo purposes
-    type WeightToFee = NoFee<<Self as pallet_balances::Config>::Balance>;
-    // Setting fee as fixed for any length of the call data for demo purposes
-    type LengthToFee = FixedFee<1, <Self as pallet_balances::Config>::Balance>;
+    type OnChargeTransaction = pallet_transaction_payment::FungibleAdapter<Balances, DealWithFees>;
+    type WeightToFee = WeightToFee;
+    type LengthToFee = ConstantMultiplier<Balance, TransactionByteFee>;
+    type FeeMultiplierUpdate = TargetedFeeAdjustment<
+        Self,
+        TargetBlockFullness,
+        AdjustmentVariable,
+        MinimumMultiplier,
+        MaximumMultiplier,
+    >;
+    type OperationalFeeMultiplier = OperationalFeeMultiplier;
 }
 
 //pallet_minimal_template
 parameter_types! {
     pub AccumulationPeriod: BlockNumber = 20;
-    pub const FaucetAmount: Balance = 250_u64;
+    pub const FaucetAmount: Balance = 250 * UNITS;
     pub const FaucetPalletId: PalletId = PalletId(*b"ATFAUCET");
+    pub const MaxMintPerCall: Option<Balance> = Some(10_000 * UNITS);
+    pub const MaxTotalMint: Option<Balance> = Some(1_000_000 * UNITS);
+    pub const PowMaxAge: BlockNumber = 10;
+    pub const MaxQueueLen: u32 = 64;
+    pub const MaxPayoutsPerBlock: u32 = 8;
+    pub const QueuedDripLifetime: BlockNumber = 100;
+    pub const MaxListUpdate: u32 = 256;
+    /// Funds of the reserve sub-account of the faucet, the source of `TopUpSource::Reserve`.
+    pub TopUpReserve: Option<AccountId> =
+        Some(FaucetPalletId::get().into_sub_account_truncating(*b"reserve"));
 }
 
 impl pallet_minimal_template::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
     type Currency = Balances;
     type AccumulationPeriod = AccumulationPeriod;
     type FaucetAmount = FaucetAmount;
     type PalletId = FaucetPalletId;
+    type AdminOrigin = frame_system::EnsureRoot<AccountId>;
+    type MaxMintPerCall = MaxMintPerCall;
+    type MaxTotalMint = MaxTotalMint;
+    type PowMaxAge = PowMaxAge;
+    type OperatorSignature = Signature;
+    type MaxOperators = frame_support::traits::ConstU32<8>;
+    type AssetId = AssetId;
+    type Assets = Assets;
+    type MaxQueueLen = MaxQueueLen;
+    type MaxPayoutsPerBlock = MaxPayoutsPerBlock;
+    type QueuedDripLifetime = QueuedDripLifetime;
+    type MaxListUpdate = MaxListUpdate;
+    type Vesting = Vesting;
+    type MinVestedTransfer = MinVestedTransfer;
+    type MaxRequestsCheckedPerBlock = frame_support::traits::ConstU32<64>;
+    type MaxClaimCodesPerCall = frame_support::traits::ConstU32<256>;
+    type TopUpReserve = TopUpReserve;
 }
 
 // Implements the types required for the template pallet.
 type Block = frame::runtime::types_common::BlockOf<Runtime, SignedExtra>;
 type Header = HeaderFor<Runtime>;
 
-type RuntimeExecutive =
-    Executive<Runtime, Block, frame_system::ChainContext<Runtime>, Runtime, AllPalletsWithSystem>;
+/// Migrations run by the next runtime upgrade. Each one is a `VersionedMigration` that only runs
+/// while the on-chain storage version of its pallet matches, so a migration can be removed once
+/// every chain has been upgraded past it.
+///
+/// The faucet totals were `u64` before the faucet was versioned.
+pub type Migrations = (pallet_minimal_template::migrations::v1::MigrateToV1<Runtime, u64>,);
+
+type RuntimeExecutive = Executive<
+    Runtime,
+    Block,
+    frame_system::ChainContext<Runtime>,
+    Runtime,
+    AllPalletsWithSystem,
+    Migrations,
+>;
 
 impl_runtime_apis! {
     impl apis::Core<Block> for Runtime {
@@ -347,14 +562,14 @@ impl_runtime_apis! {
     }
 
     impl apis::SessionKeys<Block> for Runtime {
-        fn generate_session_keys(_seed: Option<Vec<u8>>) -> Vec<u8> {
-            Default::default()
+        fn generate_session_keys(seed: Option<Vec<u8>>) -> Vec<u8> {
+            SessionKeys::generate(seed)
         }
 
         fn decode_session_keys(
-            _encoded: Vec<u8>,
+            encoded: Vec<u8>,
         ) -> Option<Vec<(Vec<u8>, apis::KeyTypeId)>> {
-            Default::default()
+            SessionKeys::decode_into_raw_public_keys(&encoded)
         }
     }
 
@@ -382,17 +597,77 @@ impl_runtime_apis! {
         }
     }
 
+    impl hooks_pallet::runtime_api::HooksApi<Block, BlockNumber> for Runtime {
+        fn payload(block_number: BlockNumber) -> Option<Vec<u8>> {
+            Hook::payload(block_number)
+        }
+    }
+
+    impl pallet_minimal_template::runtime_api::FaucetApi<
+        Block,
+        AccountId,
+        AssetId,
+        Balance,
+        BlockNumber,
+    > for Runtime {
+        fn remaining_budget(
+            asset: pallet_minimal_template::AssetKind<AssetId>,
+        ) -> Option<Balance> {
+            Template::remaining_budget(&asset)
+        }
+
+        fn remaining_allowance(
+            asset: pallet_minimal_template::AssetKind<AssetId>,
+            who: AccountId,
+        ) -> Option<Balance> {
+            Template::allowance(&asset, &who).map(|(remaining, _)| remaining)
+        }
+
+        fn window_reset(
+            asset: pallet_minimal_template::AssetKind<AssetId>,
+            who: AccountId,
+        ) -> Option<BlockNumber> {
+            Template::allowance(&asset, &who).map(|(_, reset_at)| reset_at)
+        }
+
+        fn faucet_balance(asset: pallet_minimal_template::AssetKind<AssetId>) -> Balance {
+            Template::faucet_balance(&asset)
+        }
+
+        fn queue_position(who: AccountId) -> Option<u32> {
+            Template::queue_position(&who)
+        }
+    }
+
+    #[cfg(feature = "try-runtime")]
+    impl frame_try_runtime::TryRuntime<Block> for Runtime {
+        fn on_runtime_upgrade(checks: frame_try_runtime::UpgradeCheckSelect) -> (Weight, Weight) {
+            let weight = RuntimeExecutive::try_runtime_upgrade(checks).unwrap();
+            (weight, <Runtime as frame_system::Config>::BlockWeights::get().max_block)
+        }
+
+        fn execute_block(
+            block: Block,
+            state_root_check: bool,
+            signature_check: bool,
+            select: frame_try_runtime::TryStateSelect,
+        ) -> Weight {
+            RuntimeExecutive::try_execute_block(block, state_root_check, signature_check, select)
+                .expect("execute-block failed")
+        }
+    }
+
     impl sp_genesis_builder::GenesisBuilder<Block> for Runtime {
         fn build_state(config: Vec<u8>) -> sp_genesis_builder::Result {
             build_state::<RuntimeGenesisConfig>(config)
         }
 
         fn get_preset(id: &Option<sp_genesis_builder::PresetId>) -> Option<Vec<u8>> {
-            get_preset::<RuntimeGenesisConfig>(id, |_| None)
+            get_preset::<RuntimeGenesisConfig>(id, genesis_config_presets::get_preset)
         }
 
         fn preset_names() -> Vec<sp_genesis_builder::PresetId> {
-            vec![]
+            genesis_config_presets::preset_names()
         }
     }
 }
@@ -411,6 +686,8 @@ pub mod interface {
     pub type AccountId = <Runtime as frame_system::Config>::AccountId;
     pub type Nonce = <Runtime as frame_system::Config>::Nonce;
     pub type Hash = <Runtime as frame_system::Config>::Hash;
+    pub type BlockNumber = frame_system::pallet_prelude::BlockNumberFor<Runtime>;
     pub type Balance = <Runtime as pallet_balances::Config>::Balance;
     pub type MinimumBalance = <Runtime as pallet_balances::Config>::ExistentialDeposit;
+    pub type AssetId = super::AssetId;
 }
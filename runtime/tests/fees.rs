@@ -0,0 +1,168 @@
+//! Fees of the custom pallets' calls under the runtime fee model.
+
+use codec::Encode;
+use minimal_template_runtime::{
+    interface::{AccountId, Balance},
+    Balances, DealWithFees, ExtrinsicBaseFee, FeesToFaucet, Runtime, RuntimeCall, Template,
+    TransactionByteFee, TransactionPayment, WeightToFee, CENTS, MILLICENTS,
+};
+use pallet_transaction_payment::Multiplier;
+use polkadot_sdk::{
+    frame_support::{
+        dispatch::GetDispatchInfo,
+        traits::{
+            fungible::{Balanced, Inspect},
+            OnUnbalanced,
+        },
+        weights::{
+            constants::{ExtrinsicBaseWeight, RocksDbWeight},
+            WeightToFee as _,
+        },
+    },
+    sp_io::TestExternalities,
+    sp_runtime::{traits::Convert, BuildStorage, FixedPointNumber, Percent},
+    *,
+};
+
+fn new_test_ext() -> TestExternalities {
+    frame_system::GenesisConfig::<Runtime>::default()
+        .build_storage()
+        .unwrap()
+        .into()
+}
+
+fn fee(call: &RuntimeCall, tip: Balance) -> Balance {
+    TransactionPayment::compute_fee(call.encoded_size() as u32, &call.get_dispatch_info(), tip)
+}
+
+fn save_data_chunk(len: usize) -> RuntimeCall {
+    RuntimeCall::Hook(hooks_pallet::Call::save_data_chunk {
+        data_chunk: vec![0; len],
+        block_number: 1,
+        compression: Default::default(),
+    })
+}
+
+fn token_faucet() -> RuntimeCall {
+    RuntimeCall::Template(pallet_minimal_template::Call::token_faucet {
+        dest: AccountId::from([1; 32]),
+        asset: pallet_minimal_template::AssetKind::Native,
+        amount: 100 * CENTS,
+        pow: None,
+    })
+}
+
+fn weight_fee(call: &RuntimeCall) -> Balance {
+    WeightToFee::weight_to_fee(&call.get_dispatch_info().weight)
+}
+
+#[test]
+fn base_fee_matches_extrinsic_base_weight() {
+    let fee = WeightToFee::weight_to_fee(&ExtrinsicBaseWeight::get());
+
+    // The polynomial coefficient may round
+    assert!(fee.abs_diff(ExtrinsicBaseFee::get()) < MILLICENTS);
+}
+
+#[test]
+fn custom_pallet_calls_pay_base_weight_and_length_fees() {
+    new_test_ext().execute_with(|| {
+        let calls = [
+            RuntimeCall::Template(pallet_minimal_template::Call::refill_pallet { amount: 100 }),
+            RuntimeCall::Template(pallet_minimal_template::Call::set_pow_difficulty {
+                difficulty: 8,
+            }),
+            save_data_chunk(32),
+        ];
+
+        for call in calls {
+            let len = call.encoded_size() as u32;
+            let info = call.get_dispatch_info();
+            let details = TransactionPayment::compute_fee_details(len, &info, 0);
+            let inclusion_fee = details.inclusion_fee.expect("Calls pay fees");
+
+            assert_eq!(
+                inclusion_fee.base_fee,
+                WeightToFee::weight_to_fee(&ExtrinsicBaseWeight::get())
+            );
+            assert_eq!(
+                inclusion_fee.len_fee,
+                Balance::from(len) * TransactionByteFee::get()
+            );
+            assert_eq!(
+                inclusion_fee.adjusted_weight_fee,
+                WeightToFee::weight_to_fee(&info.weight)
+            );
+        }
+    });
+}
+
+#[test]
+fn custom_pallet_calls_pay_for_their_storage_accesses() {
+    let set_pow_difficulty =
+        RuntimeCall::Template(pallet_minimal_template::Call::set_pow_difficulty { difficulty: 8 });
+    let write_fee = WeightToFee::weight_to_fee(&RocksDbWeight::get().writes(1));
+
+    for call in [
+        set_pow_difficulty.clone(),
+        token_faucet(),
+        save_data_chunk(32),
+    ] {
+        assert!(weight_fee(&call) >= write_fee);
+    }
+    // A drip touches more storage than an admin setter
+    assert!(weight_fee(&token_faucet()) > weight_fee(&set_pow_difficulty));
+}
+
+#[test]
+fn fee_grows_with_length_and_tip() {
+    new_test_ext().execute_with(|| {
+        let short = fee(&save_data_chunk(32), 0);
+        let long = fee(&save_data_chunk(48), 0);
+        assert_eq!(long - short, 16 * TransactionByteFee::get());
+
+        assert_eq!(fee(&save_data_chunk(32), 7), short + 7);
+    });
+}
+
+#[test]
+fn fee_multiplier_adjusts_slowly() {
+    new_test_ext().execute_with(|| {
+        let next = <Runtime as pallet_transaction_payment::Config>::FeeMultiplierUpdate::convert(
+            Multiplier::one(),
+        );
+
+        // An empty block lowers the multiplier, by a small step
+        assert!(next < Multiplier::one());
+        assert!(next > Multiplier::saturating_from_rational(99_999u32, 100_000u32));
+    });
+}
+
+fn deal_with_fees(fees: Balance, tips: Balance) {
+    let fees = <Balances as Balanced<AccountId>>::issue(fees);
+    let tips = <Balances as Balanced<AccountId>>::issue(tips);
+
+    DealWithFees::on_unbalanceds([fees, tips].into_iter());
+}
+
+#[test]
+fn fees_and_tips_are_burned_by_default() {
+    new_test_ext().execute_with(|| {
+        deal_with_fees(100 * CENTS, 20 * CENTS);
+
+        assert_eq!(Balances::balance(&Template::account_id()), 0);
+        assert_eq!(Balances::total_issuance(), 0);
+    });
+}
+
+#[test]
+fn fees_to_faucet_share_is_configurable() {
+    new_test_ext().execute_with(|| {
+        FeesToFaucet::set(&Percent::from_percent(50));
+
+        deal_with_fees(100 * CENTS, 20 * CENTS);
+
+        assert_eq!(Balances::balance(&Template::account_id()), 60 * CENTS);
+        assert_eq!(Balances::total_issuance(), 60 * CENTS);
+    });
+}